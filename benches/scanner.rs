@@ -0,0 +1,37 @@
+//To run this benchmark `cargo bench --bench scanner`
+//Compares reading many numbers with `Scanner` against reading them one line at a time
+//with `InputBuilder`.
+
+use read_input::{prelude::*, Scanner};
+use std::{
+    io::{self, Cursor},
+    time::{Duration, Instant},
+};
+
+const COUNT: usize = 200_000;
+
+fn time(name: &str, f: impl Fn() -> u64) {
+    let mut best = Duration::MAX;
+    for _ in 0..5 {
+        let start = Instant::now();
+        let sum = f();
+        best = best.min(start.elapsed());
+        assert_eq!(sum, (0..COUNT as u64).sum::<u64>());
+    }
+    println!("{name:<12} {best:>12?} for {COUNT} values");
+}
+
+fn main() {
+    let data: String = (0..COUNT).map(|i| format!("{i}\n")).collect();
+
+    time("Scanner", || {
+        let mut scanner = Scanner::new(Cursor::new(data.as_bytes()));
+        (0..COUNT).map(|_| scanner.get::<u64>()).sum()
+    });
+    time("InputBuilder", || {
+        let builder = input::<u64>()
+            .reading_from(Cursor::new(data.clone()))
+            .prompting_on(io::sink());
+        (0..COUNT).map(|_| builder.get()).sum()
+    });
+}
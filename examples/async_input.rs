@@ -0,0 +1,26 @@
+//To run this example `cargo run --example async_input --features tokio --release`
+//This example shows input being fetched from inside a tokio runtime without blocking it.
+
+use read_input::prelude::*;
+use tokio::io::{stdin, stdout, BufReader};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let mut input_source = BufReader::new(stdin());
+    let mut prompt_output = stdout();
+
+    let name: String = input()
+        .msg("Please input your name: ")
+        .try_read_async(&mut input_source, &mut prompt_output)
+        .await
+        .expect("Failed to read line");
+    let age: u8 = input()
+        .repeat_msg("Please input your age: ")
+        .err("That does not look like an age. Please try again")
+        .try_read_async(&mut input_source, &mut prompt_output)
+        .await
+        .expect("Failed to read line");
+
+    println!("Hello {} aged {}!", name, age);
+    dont_disappear::enter_to_continue::default();
+}
@@ -2,7 +2,7 @@
 //Example program that tells you how long it is until the date you typed in.
 //This program is here to show that types from external crates can uses `read_input`
 
-use chrono::offset::{Local, TimeZone};
+use chrono::offset::Local;
 use chrono::prelude::*;
 use read_input::prelude::*;
 use std::str::FromStr;
@@ -14,8 +14,12 @@ impl FromStr for DateDDMMYY {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut s2 = s.to_string();
         s2.push_str(" 00:00:00");
-        match Local.datetime_from_str(s2.trim(), "%Y/%m/%d %H:%M:%S") {
-            Ok(time) => Ok(DateDDMMYY(time)),
+        match NaiveDateTime::parse_from_str(s2.trim(), "%Y/%m/%d %H:%M:%S") {
+            Ok(time) => time
+                .and_local_timezone(Local)
+                .single()
+                .map(DateDDMMYY)
+                .ok_or(()),
             Err(_) => Err(()),
         }
     }
@@ -0,0 +1,24 @@
+//To run this example `cargo run --example password --release`
+//This example shows a password being read without showing it on screen.
+
+use read_input::{prelude::*, shortcut::password};
+
+fn main() {
+    let pin: u32 = input()
+        .msg("Please input your pin: ")
+        .err("A pin is made of numbers only. Please try again")
+        .masked('*')
+        .get();
+    let password = password()
+        .msg("Please choose a password: ")
+        .add_err_test(|x| x.len() >= 8, "Passwords must be at least 8 characters")
+        .confirm_password("Please type your password again: ")
+        .get();
+
+    println!(
+        "Your pin has {} digits and your password has {} characters.",
+        pin.to_string().len(),
+        password.len()
+    );
+    dont_disappear::enter_to_continue::default();
+}
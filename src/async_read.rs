@@ -0,0 +1,198 @@
+use crate::{
+    core::{Session, Step},
+    shared::Shared,
+    InputBuilder, InputBuilderOnce, ReadInputError, Sendable, Shareable,
+};
+use std::future::Future;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+impl<T, E> InputBuilder<T, E> {
+    /// Adds a validation check on input that runs asynchronously. Requires the `tokio`
+    /// feature.
+    ///
+    /// The fallback error message is printed when the test fails. Async tests run after
+    /// all other tests and are only run by `.try_read_async()`.
+    ///
+    /// Blocking methods can't run async tests, so `.try_read()` fails with
+    /// `ReadInputError::NeedsAsync` and `.get()` panics.
+    pub fn add_async_test<F, Fut>(self, test: F) -> Self
+    where
+        T: Clone + 'static,
+        F: Fn(T) -> Fut + Shareable + 'static,
+        Fut: Future<Output = bool> + Sendable + 'static,
+    {
+        self.add_async_check_opt(move |x| {
+            let passed = test(x);
+            async move {
+                if passed.await {
+                    Ok(())
+                } else {
+                    Err(None)
+                }
+            }
+        })
+    }
+    /// Adds a validation check on input that runs asynchronously and returns its own error
+    /// message when the value fails. Requires the `tokio` feature.
+    ///
+    /// Async tests run after all other tests and are only run by `.try_read_async()`.
+    ///
+    /// Blocking methods can't run async tests, so `.try_read()` fails with
+    /// `ReadInputError::NeedsAsync` and `.get()` panics.
+    pub fn add_async_check<F, Fut>(self, check: F) -> Self
+    where
+        T: Clone + 'static,
+        F: Fn(T) -> Fut + Shareable + 'static,
+        Fut: Future<Output = Result<(), String>> + Sendable + 'static,
+    {
+        self.add_async_check_opt(move |x| {
+            let checked = check(x);
+            async move { checked.await.map_err(Some) }
+        })
+    }
+    // Internal function for adding async tests. `Err(None)` prints the fallback error.
+    fn add_async_check_opt<F, Fut>(mut self, check: F) -> Self
+    where
+        T: Clone,
+        F: Fn(T) -> Fut + Shareable + 'static,
+        Fut: Future<Output = Result<(), Option<String>>> + Sendable + 'static,
+    {
+        self.async_tests
+            .push(Shared::new(move |x: &T| Box::pin(check(x.clone()))));
+        self
+    }
+    /// 'gets' the input form the user without blocking. Requires the `tokio` feature.
+    ///
+    /// Input is read from `input_source` and prompts are written to `prompt_output`. The
+    /// writer and reader set by `.prompting_on()` and `.reading_from()` are not used.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ReadInputError` describing why input could not be read.
+    pub async fn try_read_async<R, W>(
+        &self,
+        input_source: &mut R,
+        prompt_output: &mut W,
+    ) -> Result<T, ReadInputError>
+    where
+        R: AsyncBufRead + Unpin + ?Sized,
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        read_input_async(self, None, input_source, prompt_output).await
+    }
+}
+
+impl<T, E> InputBuilderOnce<T, E> {
+    /// Adds a validation check on input that runs asynchronously. Requires the `tokio`
+    /// feature. See `InputBuilder::add_async_test`.
+    pub fn add_async_test<F, Fut>(self, test: F) -> Self
+    where
+        T: Clone + 'static,
+        F: Fn(T) -> Fut + Shareable + 'static,
+        Fut: Future<Output = bool> + Sendable + 'static,
+    {
+        self.internal(|x| x.add_async_test(test))
+    }
+    /// Adds a validation check on input that runs asynchronously and returns its own error
+    /// message when the value fails. Requires the `tokio` feature. See
+    /// `InputBuilder::add_async_check`.
+    pub fn add_async_check<F, Fut>(self, check: F) -> Self
+    where
+        T: Clone + 'static,
+        F: Fn(T) -> Fut + Shareable + 'static,
+        Fut: Future<Output = Result<(), String>> + Sendable + 'static,
+    {
+        self.internal(|x| x.add_async_check(check))
+    }
+    /// 'gets' the input form the user without blocking. Requires the `tokio` feature.
+    ///
+    /// Input is read from `input_source` and prompts are written to `prompt_output`. The
+    /// writer and reader set by `.prompting_on()` and `.reading_from()` are not used.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ReadInputError` describing why input could not be read.
+    pub async fn try_read_async<R, W>(
+        self,
+        input_source: &mut R,
+        prompt_output: &mut W,
+    ) -> Result<T, ReadInputError>
+    where
+        R: AsyncBufRead + Unpin + ?Sized,
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let (builder, default) = self.into_parts()?;
+        read_input_async(&builder, default, input_source, prompt_output).await
+    }
+}
+
+// Async version of `core::read_input`.
+async fn read_input_async<T, E, R, W>(
+    builder: &InputBuilder<T, E>,
+    default: Option<T>,
+    input_source: &mut R,
+    prompt_output: &mut W,
+) -> Result<T, ReadInputError>
+where
+    R: AsyncBufRead + Unpin + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let mut session = Session::new(builder, default)?;
+    write_flush(prompt_output, session.prompt()).await;
+
+    loop {
+        let mut input = String::new();
+        let read = input_source.read_line(&mut input);
+        let read = match builder.timeout {
+            Some(duration) => match tokio::time::timeout(duration, read).await {
+                Ok(read) => read,
+                Err(_) => return session.time_out(),
+            },
+            None => read.await,
+        };
+        let input = match read? {
+            0 => None,
+            _ => Some(input),
+        };
+
+        let step = match session.handle(input) {
+            Step::Passed(value, input) => match run_async_tests(builder, &value).await {
+                Ok(()) => session.accept(value, input),
+                Err(message) => session.reject(input, message),
+            },
+            step => step,
+        };
+
+        match step {
+            Step::Done(result) => return result,
+            Step::Confirm => write_flush(prompt_output, session.confirm_prompt()).await,
+            Step::Rejected { message, give_up } => {
+                write_flush(prompt_output, &format!("{message}\n")).await;
+                if let Some(e) = give_up {
+                    return Err(e);
+                }
+                if let Some(prompt) = session.repeat_prompt() {
+                    write_flush(prompt_output, prompt).await;
+                }
+            }
+            // `accept()` never returns `Passed`.
+            Step::Passed(..) => unreachable!(),
+        }
+    }
+}
+
+async fn run_async_tests<T, E>(
+    builder: &InputBuilder<T, E>,
+    value: &T,
+) -> Result<(), Option<String>> {
+    for test in &builder.async_tests {
+        test(value).await?;
+    }
+    Ok(())
+}
+
+// Like the blocking version, failing to write a prompt does not stop input being read.
+async fn write_flush<W: AsyncWrite + Unpin + ?Sized>(prompt_output: &mut W, msg: &str) {
+    let _ = prompt_output.write_all(msg.as_bytes()).await;
+    let _ = prompt_output.flush().await;
+}
@@ -1,70 +1,300 @@
-use crate::{Prompt, Test};
+use crate::{
+    echo::{self, Echo},
+    insert_before_suffix, interrupt, timeout, InputBuilder, RawStep, ReadInputError, Rejection,
+    MISMATCH_ERR,
+};
 use std::{
-    io::{self, Write},
-    str::FromStr,
+    io::{self, BufRead, Write},
     string::ToString,
+    time::Duration,
 };
 
-// Core function when running `.get()`.
-pub(crate) fn read_input<T: FromStr>(
-    prompt: &Prompt,
-    err: &str,
-    default: Option<T>,
-    tests: &[Test<T>],
-    err_pass: &dyn Fn(&T::Err) -> Option<String>,
-    prompt_output: &mut dyn Write,
-) -> io::Result<T> {
-    fn try_flush(prompt_output: &mut dyn Write) -> () {
-        prompt_output.flush().unwrap_or(())
-    }
+// Where `read_input` fetches lines from and reports prompts and errors to.
+// `read_line` returns `None` once the end of input is reached.
+pub(crate) trait Console {
+    fn read_line(&mut self) -> io::Result<Option<String>>;
+    fn prompt(&mut self, msg: &str);
+    fn error(&mut self, msg: &str);
+}
+
+// `Console` used by `.get()`. Reads from stdin or the source set by `.reading_from()`.
+// `echo`, `timeout` and `cancel_on_interrupt` are only set when reading from a terminal.
+pub(crate) struct Terminal<'a> {
+    pub input_source: &'a mut dyn BufRead,
+    pub prompt_output: &'a mut dyn Write,
+    pub echo: Echo,
+    pub timeout: Option<Duration>,
+    pub countdown: bool,
+    pub cancel_on_interrupt: bool,
+    // Number of characters written on the current line. Used to draw `.countdown()`.
+    pub column: usize,
+}
 
-    fn input_as_string() -> io::Result<String> {
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        Ok(input)
+impl Console for Terminal<'_> {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let _catch = if self.cancel_on_interrupt {
+            Some(interrupt::Catch::new()?)
+        } else {
+            None
+        };
+        // Interrupted line reads are restarted by `BufRead`, so stdin is waited on first
+        // to see Ctrl-C.
+        let wait = self.timeout.is_some() || self.cancel_on_interrupt;
+        let (timeout, countdown, column) = (self.timeout, self.countdown, self.column);
+        let line = echo::read_line(
+            self.input_source,
+            self.prompt_output,
+            self.echo,
+            |prompt_output| {
+                if wait {
+                    timeout::wait_for_stdin(timeout, prompt_output, countdown, column)
+                } else {
+                    Ok(())
+                }
+            },
+        );
+        if matches!(&line, Err(e) if e.kind() == io::ErrorKind::Interrupted) {
+            // The line was not finished so the cursor is still after the input.
+            let _ = writeln!(self.prompt_output);
+        }
+        // Every way of finishing a read leaves the cursor at the start of a new line.
+        self.column = 0;
+        line
+    }
+    fn prompt(&mut self, msg: &str) {
+        self.column = match msg.rsplit_once('\n') {
+            Some((_, line)) => line.chars().count(),
+            None => self.column + msg.chars().count(),
+        };
+        let _ = write!(self.prompt_output, "{msg}");
+        self.prompt_output.flush().unwrap_or(());
     }
+    fn error(&mut self, msg: &str) {
+        self.column = 0;
+        let _ = writeln!(self.prompt_output, "{msg}");
+    }
+}
 
-    let _ = write!(prompt_output, "{}", prompt.msg);
-    try_flush(prompt_output);
+// State of a single `.get()` call. Shared by the blocking and async loops so both treat
+// input the same way.
+pub(crate) struct Session<'a, T, E> {
+    builder: &'a InputBuilder<T, E>,
+    // Rendered when the session starts so it still shows the default after it is used.
+    prompt: String,
+    default: Option<T>,
+    failures: usize,
+    // Value waiting for `.confirm_password()` and the input line that must be repeated.
+    pending: Option<(T, String)>,
+}
 
-    loop {
-        let input = input_as_string()?;
+pub(crate) enum Step<T> {
+    Done(Result<T, ReadInputError>),
+    // Input was parsed and passed all tests. Holds the trimmed input line so the value
+    // can still be rejected by async tests and compared with `.confirm_password()` input.
+    Passed(T, String),
+    // Input was accepted but must be typed again. `Session::confirm_prompt()` is printed.
+    Confirm,
+    // Input was rejected. `message` is printed, then `give_up` is returned if it is set.
+    Rejected {
+        message: String,
+        give_up: Option<ReadInputError>,
+    },
+}
 
-        if input.trim().is_empty() {
-            if let Some(x) = default {
-                return Ok(x);
+impl<'a, T, E> Session<'a, T, E> {
+    // Fails with `ReadInputError::InvalidDefault` if the default from `.default_with()`
+    // fails the tests.
+    pub fn new(
+        builder: &'a InputBuilder<T, E>,
+        default: Option<T>,
+    ) -> Result<Self, ReadInputError> {
+        let default = match (default, &builder.default_with) {
+            (None, Some(default_with)) => {
+                let default = default_with();
+                builder
+                    .test_value(&default)
+                    .map_err(ReadInputError::InvalidDefault)?;
+                Some(default)
             }
+            (default, _) => default,
         };
-
-        match parse_input(input, err, tests, err_pass) {
-            Ok(v) => return Ok(v),
-            Err(e) => {
-                let _ = writeln!(prompt_output, "{}", e);
+        let mut prompt = match &builder.prompt_fmt {
+            Some(prompt_fmt) => prompt_fmt(&builder.msg.msg, default.as_ref()),
+            None => builder.msg.msg.clone(),
+        };
+        if let (Some(show_default), Some(default)) = (&builder.show_default, &default) {
+            if !builder.msg.msg.is_empty() {
+                prompt = insert_before_suffix(&prompt, &show_default(default));
+            }
+        }
+        Ok(Self {
+            builder,
+            prompt,
+            default,
+            failures: 0,
+            pending: None,
+        })
+    }
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+    // Prompt printed after input is rejected.
+    pub fn repeat_prompt(&self) -> Option<&str> {
+        if self.builder.msg.repeat {
+            Some(self.prompt())
+        } else {
+            None
+        }
+    }
+    pub fn confirm_prompt(&self) -> &'a str {
+        self.builder.confirm.as_deref().unwrap_or_default()
+    }
+    // Handles a line of input. `None` means the end of input has been reached.
+    pub fn handle(&mut self, input: Option<String>) -> Step<T> {
+        if let (Some(word), Some(input)) = (&self.builder.cancel_word, &input) {
+            if input.trim() == word {
+                return Step::Done(Err(ReadInputError::Cancelled));
             }
+        }
+
+        if let Some((value, expected)) = self.pending.take() {
+            return match input {
+                Some(input) if input.trim() == expected => Step::Done(Ok(value)),
+                Some(input) => {
+                    self.reject(input.trim().to_string(), Some(MISMATCH_ERR.to_string()))
+                }
+                None => Step::Done(Err(ReadInputError::Eof)),
+            };
+        }
+
+        let Some(input) = input else {
+            return Step::Done(match self.default.take() {
+                Some(x) if self.builder.eof_default => Ok(x),
+                _ => Err(ReadInputError::Eof),
+            });
         };
 
-        if prompt.repeat {
-            let _ = write!(prompt_output, "{}", prompt.msg);
-            try_flush(prompt_output)
+        if input.trim().is_empty() {
+            if let Some(x) = self.default.take() {
+                return Step::Done(Ok(x));
+            }
+        }
+
+        let trimmed = input.trim().to_string();
+        match parse_input(input, self.builder) {
+            Ok(v) => Step::Passed(v, trimmed),
+            Err(message) => self.reject(trimmed, Some(message)),
+        }
+    }
+    // Called when no input is entered before `.timeout()` ends.
+    pub fn time_out(&mut self) -> Result<T, ReadInputError> {
+        match self.default.take() {
+            Some(x) if self.pending.is_none() => Ok(x),
+            _ => Err(ReadInputError::TimedOut),
+        }
+    }
+    // Called with input that has passed every test. Asks for the input again when
+    // `.confirm_password()` is used.
+    pub fn accept(&mut self, value: T, input: String) -> Step<T> {
+        if self.builder.confirm.is_some() {
+            self.pending = Some((value, input));
+            Step::Confirm
+        } else {
+            Step::Done(Ok(value))
+        }
+    }
+    pub fn reject(&mut self, input: String, message: Option<String>) -> Step<T> {
+        let message = message.unwrap_or_else(|| self.builder.err.clone());
+        self.failures += 1;
+        let give_up = if self
+            .builder
+            .attempts
+            .is_some_and(|max| self.failures >= max)
+        {
+            Some(ReadInputError::TooManyAttempts {
+                // Hidden input is not kept so it can't end up in logs or panic messages.
+                input: if self.builder.echo == Echo::Visible {
+                    input
+                } else {
+                    String::new()
+                },
+                message: message.clone(),
+            })
+        } else {
+            None
         };
+        Step::Rejected { message, give_up }
     }
 }
 
-pub(crate) fn parse_input<T: FromStr>(
-    input: String,
-    err: &str,
-    tests: &[Test<T>],
-    err_pass: &dyn Fn(&T::Err) -> Option<String>,
-) -> Result<T, String> {
-    match T::from_str(&input.trim()) {
-        Ok(value) => {
-            for test in tests {
-                if !(test.func)(&value) {
-                    return Err(test.err.clone().unwrap_or_else(|| err.to_string()));
+// Core function when running `.get()`.
+pub(crate) fn read_input<T, E>(
+    builder: &InputBuilder<T, E>,
+    default: Option<T>,
+    console: &mut dyn Console,
+) -> Result<T, ReadInputError> {
+    #[cfg(feature = "tokio")]
+    if !builder.async_tests.is_empty() {
+        return Err(ReadInputError::NeedsAsync);
+    }
+
+    let mut session = Session::new(builder, default)?;
+    console.prompt(session.prompt());
+
+    loop {
+        let input = match console.read_line() {
+            Err(e) if e.kind() == io::ErrorKind::TimedOut && builder.timeout.is_some() => {
+                return session.time_out()
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted && builder.cancel_on_interrupt => {
+                return Err(ReadInputError::Cancelled)
+            }
+            input => input?,
+        };
+        let step = match session.handle(input) {
+            Step::Passed(value, input) => session.accept(value, input),
+            step => step,
+        };
+
+        match step {
+            Step::Done(result) => return result,
+            Step::Confirm => console.prompt(session.confirm_prompt()),
+            Step::Rejected { message, give_up } => {
+                console.error(&message);
+                if let Some(e) = give_up {
+                    return Err(e);
+                }
+                if let Some(prompt) = session.repeat_prompt() {
+                    console.prompt(prompt);
                 }
             }
-            Ok(value)
+            // `accept()` never returns `Passed`.
+            Step::Passed(..) => unreachable!(),
         }
-        Err(error) => Err(err_pass(&error).unwrap_or_else(|| err.to_string())),
     }
 }
+
+pub(crate) fn parse_input<T, E>(input: String, builder: &InputBuilder<T, E>) -> Result<T, String> {
+    let mut input = input.trim().to_string();
+    for step in &builder.raw_steps {
+        match step {
+            RawStep::Check(check) => check(&input)?,
+            RawStep::Normalize(normalize) => input = normalize(&input),
+        }
+    }
+
+    (builder.parse)(&input)
+        .and_then(|value| {
+            for test in &builder.tests {
+                test.run(&value).map_err(Rejection::Msg)?;
+            }
+            Ok(value)
+        })
+        .map_err(|rejection| {
+            match rejection {
+                Rejection::Parse(error) => (builder.err_match)(&error),
+                Rejection::Msg(msg) => msg,
+            }
+            .unwrap_or_else(|| builder.err.clone())
+        })
+}
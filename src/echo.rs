@@ -0,0 +1,166 @@
+// Reading input without showing it. Used by `.hidden()` and `.masked()`.
+//
+// Echo can only be turned off when input is read from stdin and stdin is a terminal on
+// Unix. Everywhere else input is read as normal.
+
+#[cfg(any(unix, test))]
+use crate::interrupt;
+use std::io::{self, BufRead, Write};
+
+// How typed input is shown.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Echo {
+    Visible,
+    Hidden,
+    // Each character typed is shown as the mask character. The mask is only used on Unix.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    Masked(char),
+}
+
+// Reads a line from a terminal on stdin with echo set by `echo`. `wait` is run once echo
+// has been turned off so nothing typed while waiting is shown.
+#[cfg(unix)]
+pub(crate) fn read_line(
+    input_source: &mut dyn BufRead,
+    prompt_output: &mut dyn Write,
+    echo: Echo,
+    wait: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<Option<String>> {
+    let _guard = match echo {
+        Echo::Visible => None,
+        Echo::Hidden => Some(EchoOff::new(false)?),
+        Echo::Masked(_) => Some(EchoOff::new(true)?),
+    };
+    wait(prompt_output)?;
+    match echo {
+        Echo::Visible | Echo::Hidden => read_plain(input_source),
+        Echo::Masked(mask) => {
+            let line = read_masked(input_source, prompt_output, mask)?;
+            // The terminal no longer echoes the newline typed by the user.
+            let _ = writeln!(prompt_output);
+            Ok(line)
+        }
+    }
+}
+
+// Without a way to turn off echo input is read as normal.
+#[cfg(not(unix))]
+pub(crate) fn read_line(
+    input_source: &mut dyn BufRead,
+    prompt_output: &mut dyn Write,
+    _echo: Echo,
+    wait: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<Option<String>> {
+    wait(prompt_output)?;
+    read_plain(input_source)
+}
+
+pub(crate) fn read_plain(input_source: &mut dyn BufRead) -> io::Result<Option<String>> {
+    let mut input = String::new();
+    if input_source.read_line(&mut input)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(input))
+}
+
+// Reads a line one byte at a time, printing `mask` for each character. Backspace removes
+// the last character. Expects the terminal to be in non-canonical mode.
+#[cfg(any(unix, test))]
+pub(crate) fn read_masked(
+    input_source: &mut dyn BufRead,
+    prompt_output: &mut dyn Write,
+    mask: char,
+) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    let mut byte = [0];
+    loop {
+        let read = match input_source.read(&mut byte) {
+            // Only Ctrl-C caught by `.cancel_on_interrupt()` stops reading.
+            Err(e) if e.kind() == io::ErrorKind::Interrupted && !interrupt::take() => continue,
+            read => read?,
+        };
+        if read == 0 {
+            if line.is_empty() {
+                return Ok(None);
+            }
+            break;
+        }
+        match byte[0] {
+            b'\n' | b'\r' => break,
+            // Ctrl-D on an empty line ends input like it does in canonical mode.
+            4 if line.is_empty() => return Ok(None),
+            8 | 127 => {
+                if pop_char(&mut line) {
+                    let _ = write!(prompt_output, "\u{8} \u{8}");
+                }
+            }
+            b => {
+                line.push(b);
+                // Only the first byte of a UTF-8 character is masked.
+                if b & 0xC0 != 0x80 {
+                    let _ = write!(prompt_output, "{mask}");
+                }
+            }
+        }
+        let _ = prompt_output.flush();
+    }
+    String::from_utf8(line)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Removes the last UTF-8 character from `line`. Returns `false` if `line` was empty.
+#[cfg(any(unix, test))]
+fn pop_char(line: &mut Vec<u8>) -> bool {
+    while let Some(b) = line.pop() {
+        if b & 0xC0 != 0x80 {
+            return true;
+        }
+    }
+    false
+}
+
+// Turns off echo on stdin until dropped.
+#[cfg(unix)]
+struct EchoOff {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl EchoOff {
+    // With `by_byte` set canonical mode is also turned off so keys are read as they are
+    // pressed.
+    fn new(by_byte: bool) -> io::Result<Self> {
+        // SAFETY: `termios` is a plain C struct that `tcgetattr` fills in.
+        let mut term: libc::termios = unsafe { std::mem::zeroed() };
+        // SAFETY: `term` is a valid pointer for the duration of the call.
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &raw mut term) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let original = term;
+        term.c_lflag &= !libc::ECHO;
+        if by_byte {
+            term.c_lflag &= !libc::ICANON;
+            term.c_cc[libc::VMIN] = 1;
+            term.c_cc[libc::VTIME] = 0;
+        } else {
+            // The newline is still shown so the cursor moves on after input.
+            term.c_lflag |= libc::ECHONL;
+        }
+        // SAFETY: `term` is a valid pointer for the duration of the call.
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw const term) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { original })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        // SAFETY: `original` is a valid pointer for the duration of the call.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw const self.original);
+        }
+    }
+}
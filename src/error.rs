@@ -0,0 +1,112 @@
+use std::{error::Error, fmt, io};
+
+/// Reasons fetching input can fail. Returned by `.try_read()`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReadInputError {
+    /// The end of input was reached before a valid value was read.
+    Eof,
+    /// The input source was closed or disconnected while reading.
+    Closed(io::Error),
+    /// Reading was interrupted before a line could be read.
+    Interrupted,
+    /// The user cancelled input with Ctrl-C or the word set by `.cancel_word()`. Only
+    /// returned after `.cancel_on_interrupt()` or `.cancel_word()` is used.
+    Cancelled,
+    /// The builder has async tests, so input must be fetched with `.try_read_async()`.
+    /// Returned by blocking methods such as `.try_read()`.
+    NeedsAsync,
+    /// Any other error produced by the input source.
+    Io(io::Error),
+    /// The default value did not pass the builder's tests. Holds the error message of the
+    /// test it failed. Returned for defaults from `.default_with()`, and for defaults from
+    /// `.try_default()` that fail tests added after it.
+    InvalidDefault(String),
+    /// No input was entered before the time set by `.timeout()` ended and there was no
+    /// default value.
+    TimedOut,
+    /// Input was rejected more times than allowed by `.attempts()`.
+    TooManyAttempts {
+        /// The last rejected input with surrounding whitespace removed. Empty when input is
+        /// hidden with `.hidden()` or `.masked()`.
+        input: String,
+        /// The error message printed for the last rejected input.
+        message: String,
+    },
+}
+
+impl fmt::Display for ReadInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eof => write!(f, "reached end of input"),
+            Self::Closed(e) => write!(f, "input source was closed: {e}"),
+            Self::Interrupted => write!(f, "reading input was interrupted"),
+            Self::Cancelled => write!(f, "input was cancelled"),
+            Self::NeedsAsync => write!(f, "input with async tests must be read asynchronously"),
+            Self::InvalidDefault(message) => write!(f, "default value is invalid: {message}"),
+            Self::TimedOut => write!(f, "timed out waiting for input"),
+            Self::Io(e) => write!(f, "failed to read input: {e}"),
+            Self::TooManyAttempts { message, .. } => {
+                write!(f, "too many invalid inputs: {message}")
+            }
+        }
+    }
+}
+
+impl Error for ReadInputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Closed(e) | Self::Io(e) => Some(e),
+            Self::Eof
+            | Self::Interrupted
+            | Self::Cancelled
+            | Self::NeedsAsync
+            | Self::InvalidDefault(_)
+            | Self::TimedOut
+            | Self::TooManyAttempts { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadInputError {
+    fn from(error: io::Error) -> Self {
+        match error.get_ref().and_then(|e| e.downcast_ref()) {
+            Some(Self::Cancelled) => return Self::Cancelled,
+            Some(Self::NeedsAsync) => return Self::NeedsAsync,
+            _ => {}
+        }
+        match error.kind() {
+            io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected => Self::Closed(error),
+            io::ErrorKind::Interrupted => Self::Interrupted,
+            io::ErrorKind::TimedOut => Self::TimedOut,
+            _ => Self::Io(error),
+        }
+    }
+}
+
+impl From<ReadInputError> for io::Error {
+    fn from(error: ReadInputError) -> Self {
+        match error {
+            ReadInputError::Closed(e) | ReadInputError::Io(e) => e,
+            ReadInputError::Eof => io::ErrorKind::UnexpectedEof.into(),
+            ReadInputError::Interrupted => io::ErrorKind::Interrupted.into(),
+            // Kept inside the error so it can be told apart from other interruptions.
+            ReadInputError::Cancelled => {
+                io::Error::new(io::ErrorKind::Interrupted, ReadInputError::Cancelled)
+            }
+            ReadInputError::NeedsAsync => {
+                io::Error::new(io::ErrorKind::Unsupported, ReadInputError::NeedsAsync)
+            }
+            ReadInputError::InvalidDefault(message) => {
+                io::Error::new(io::ErrorKind::InvalidInput, message)
+            }
+            ReadInputError::TimedOut => io::ErrorKind::TimedOut.into(),
+            ReadInputError::TooManyAttempts { message, .. } => {
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Catching Ctrl-C while input is read. Used by `.cancel_on_interrupt()`.
+//
+// Ctrl-C can only be caught when stdin is a terminal on Unix. Everywhere else it ends the
+// program as normal.
+
+use std::io;
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(unix)]
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+// Returns `true` if Ctrl-C was pressed since the last call.
+#[cfg(unix)]
+pub(crate) fn take() -> bool {
+    INTERRUPTED.swap(false, Ordering::SeqCst)
+}
+
+// Only used by `echo::read_masked()`, which is tested on every platform.
+#[cfg(all(not(unix), test))]
+pub(crate) fn take() -> bool {
+    false
+}
+
+// Catches Ctrl-C until dropped. Blocking reads fail with `io::ErrorKind::Interrupted`
+// instead of being restarted when Ctrl-C is pressed.
+#[cfg(unix)]
+pub(crate) struct Catch {
+    original: libc::sigaction,
+}
+
+#[cfg(unix)]
+impl Catch {
+    pub fn new() -> io::Result<Self> {
+        take();
+        // SAFETY: `sigaction` is a plain C struct where all zeros is a valid value.
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        // SAFETY: `original` is filled in by `sigaction` below.
+        let mut original: libc::sigaction = unsafe { std::mem::zeroed() };
+        // SAFETY: both pointers are valid for the duration of the call. `SA_RESTART` is not
+        // set so reads are interrupted.
+        if unsafe { libc::sigaction(libc::SIGINT, &raw const action, &raw mut original) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { original })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Catch {
+    fn drop(&mut self) {
+        // SAFETY: `original` is a valid pointer for the duration of the call.
+        unsafe {
+            libc::sigaction(libc::SIGINT, &raw const self.original, std::ptr::null_mut());
+        }
+    }
+}
+
+// Without a way to catch Ctrl-C it ends the program as normal.
+#[cfg(not(unix))]
+pub(crate) struct Catch;
+
+#[cfg(not(unix))]
+impl Catch {
+    // Returns a `Result` like the Unix version.
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn new() -> io::Result<Self> {
+        Ok(Self)
+    }
+}
@@ -1,26 +1,90 @@
 //! Go the the [readme](https://crates.io/crates/read_input) file for extra documentation and tutorial.
 
 #![deny(clippy::pedantic, missing_docs)]
-#![allow(clippy::must_use_candidate)]
+#![allow(clippy::must_use_candidate, clippy::return_self_not_must_use)]
 // `impl ToString` is better than `&impl ToString`. Clippy is not ready for impl trait.
 #![allow(clippy::needless_pass_by_value)]
 
+#[cfg(feature = "tokio")]
+mod async_read;
 mod core;
+mod echo;
+mod error;
+mod interrupt;
 pub mod prelude;
+mod scanner;
+mod shared;
 pub mod shortcut;
 mod test_generators;
+pub mod testing;
 #[cfg(test)]
 mod tests;
+mod timeout;
+mod tuple;
 
-use crate::{core::read_input, test_generators::InsideFunc};
-use std::cell::RefCell;
-use std::io::Write;
-use std::{cmp::PartialOrd, io, rc::Rc, str::FromStr, string::ToString};
+pub use crate::{
+    error::ReadInputError,
+    scanner::{ScanError, Scanner},
+    shared::{Sendable, Shareable},
+    tuple::{CountError, TupleBuild, TupleInput},
+};
+
+#[cfg(feature = "tokio")]
+use crate::shared::AsyncTestFn;
+
+/// Creates an `InputBuilder` for a line in a format such as `"{} x {}"`, where each `{}` is
+/// replaced by a value of the matching type.
+///
+/// The builder returns a tuple of the values, or the value itself when there is only one
+/// type. Lines that do not match the format or have a value that fails to parse are
+/// rejected with the `.err()` message.
+///
+/// ```no_run
+/// use read_input::prelude::*;
+///
+/// let (width, height) = read_input::input!("{} x {}", u32, u32)
+///     .msg("Please input a size: ")
+///     .get();
+/// ```
+#[macro_export]
+macro_rules! input {
+    ($format:expr, $t:ty $(,)?) => {
+        $crate::shortcut::scan::<($t,)>($format).map(|(value,)| value)
+    };
+    ($format:expr, $($t:ty),+ $(,)?) => {
+        $crate::shortcut::scan::<($($t,)+)>($format)
+    };
+}
+
+use crate::{
+    core::{parse_input, read_input, Console, Terminal},
+    echo::Echo,
+    shared::{
+        lock, CheckFn, DefaultFn, DisplayFn, ErrMatch, InputSource, NormalizeFn, Parser, PromptFn,
+        PromptOutput, Shared, TestFunc,
+    },
+    test_generators::InsideFunc,
+};
+use std::{
+    cmp::PartialOrd,
+    convert::TryInto,
+    fmt::Display,
+    io::{self, BufRead, IsTerminal, Write},
+    str::FromStr,
+    string::ToString,
+    time::Duration,
+};
 
 const DEFAULT_ERR: &str = "That value does not pass. Please try again";
+const MISMATCH_ERR: &str = "Inputs do not match. Please try again";
+const DEFAULT_TEMPLATE: &str = " [{}]";
 
 /// Trait for common types that store input settings.
-pub trait InputBuild<T: FromStr> {
+pub trait InputBuild<T> {
+    /// Error type produced when parsing input fails. This is `T::Err` for builders that
+    /// parse with `from_str()`.
+    type ParseErr;
+
     /// Changes or adds a prompt message that gets printed once when input if fetched.
     fn msg(self, msg: impl ToString) -> Self;
     /// Changes or adds a prompt message and that is repeated each time input is requested.
@@ -28,19 +92,35 @@ pub trait InputBuild<T: FromStr> {
     /// Changes fallback error message.
     fn err(self, err: impl ToString) -> Self;
     /// Adds a validation check on input.
-    fn add_test<F: Fn(&T) -> bool + 'static>(self, test: F) -> Self;
+    fn add_test<F: Fn(&T) -> bool + Shareable + 'static>(self, test: F) -> Self;
     /// Adds a validation check on input with a custom error message printed when the test
     /// fails.
     fn add_err_test<F>(self, test: F, err: impl ToString) -> Self
     where
-        F: Fn(&T) -> bool + 'static;
+        F: Fn(&T) -> bool + Shareable + 'static;
+    /// Adds a validation check on input that returns its own error message when the
+    /// value fails.
+    fn add_check<F>(self, check: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), String> + Shareable + 'static;
+    /// Adds a validation check on the raw input line that runs before it is parsed.
+    /// The check returns the error message to print when the line fails.
+    fn add_raw_check<F>(self, check: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Shareable + 'static;
+    /// Rewrites the raw input line before it is parsed. Runs in order with
+    /// `.add_raw_check()`.
+    fn normalize<F: Fn(&str) -> String + Shareable + 'static>(self, normalize: F) -> Self;
     /// Removes all validation checks made by `.add_test()`, `.add_err_test()`,
-    /// `.inside()` and `.inside_err()`.
+    /// `.add_check()`, `.inside()`, `.inside_err()` and the async test methods.
+    ///
+    /// Only tests added since the last `.map()`, `.and_then()` or `.list()` are removed.
+    /// Tests added before them still run on the value they were added for.
     fn clear_tests(self) -> Self;
     /// Used specify custom error messages that depend on the errors produced by `from_str()`.
     fn err_match<F>(self, err_match: F) -> Self
     where
-        F: Fn(&T::Err) -> Option<String> + 'static;
+        F: Fn(&Self::ParseErr) -> Option<String> + Shareable + 'static;
     /// Ensures that input is within a range, array or vector.
     fn inside<U: InsideFunc<T>>(self, constraint: U) -> Self;
     /// Ensures that input is within a range, array or vector with a custom error message
@@ -49,16 +129,54 @@ pub trait InputBuild<T: FromStr> {
     /// Toggles whether a prompt message gets printed once or each time input is requested.
     fn toggle_msg_repeat(self) -> Self;
     /// Send prompts to custom writer instead of stdout
-    fn prompting_on(self, prompt_output: RefCell<Box<dyn Write>>) -> Self;
+    fn prompting_on(self, prompt_output: impl Write + Sendable + 'static) -> Self;
     /// Send prompts to stderr instead of stdout
     fn prompting_on_stderr(self) -> Self;
+    /// Read input from custom reader instead of stdin. Clones of the builder read from
+    /// the same reader.
+    fn reading_from(self, input_source: impl BufRead + Sendable + 'static) -> Self;
+    /// Sets whether the default value is returned when the end of input is reached.
+    ///
+    /// When disabled or when no default is set, reaching the end of input fails with
+    /// `ReadInputError::Eof`. Enabled by default.
+    fn eof_default(self, use_default: bool) -> Self;
+    /// Limits how many times invalid input is accepted before giving up with
+    /// `ReadInputError::TooManyAttempts`. A limit of `0` behaves like `1`.
+    fn attempts(self, attempts: usize) -> Self;
+    /// Stops input being shown while it is typed. Used for passwords.
+    ///
+    /// Echo is only turned off when reading from stdin and stdin is a terminal on Unix.
+    /// Piped input, input set with `.reading_from()` and input on other platforms is read
+    /// as normal.
+    fn hidden(self) -> Self;
+    /// Like `.hidden()` but shows `mask` for each character typed.
+    fn masked(self, mask: char) -> Self;
+    /// Asks for input a second time with the prompt `msg` once it is valid. If the two
+    /// inputs do not match the user is asked again from the start.
+    fn confirm_password(self, msg: impl ToString) -> Self;
+    /// Stops waiting for input after `timeout` and returns the default value. Fails with
+    /// `ReadInputError::TimedOut` if there is no default value.
+    ///
+    /// The time limit starts each time input is requested. It only applies when reading
+    /// from stdin and stdin is a terminal on Unix, or when using `.try_read_async()`.
+    fn timeout(self, timeout: Duration) -> Self;
+    /// Shows the number of seconds left before `.timeout()` ends after the prompt.
+    fn countdown(self) -> Self;
+    /// Makes Ctrl-C fail with `ReadInputError::Cancelled` instead of ending the program,
+    /// so settings such as `.hidden()` are undone before the program exits.
+    ///
+    /// Ctrl-C is only caught when reading from stdin and stdin is a terminal on Unix. It
+    /// is not caught by `.try_read_async()`.
+    fn cancel_on_interrupt(self) -> Self;
+    /// Fails with `ReadInputError::Cancelled` when `word`, such as `:q`, is entered.
+    fn cancel_word(self, word: impl ToString) -> Self;
 }
 
 /// Trait for changing input settings by adding constraints that require `PartialOrd`
 /// on the input type.
 pub trait InputConstraints<T>: InputBuild<T>
 where
-    T: FromStr + PartialOrd + 'static,
+    T: PartialOrd + Shareable + 'static,
     Self: Sized,
 {
     /// Sets a minimum input value.
@@ -95,16 +213,148 @@ where
     }
 }
 
+/// Trait for changing input settings by adding constraints on input that is a list of
+/// values.
+pub trait ListConstraints<T>: InputBuild<Vec<T>>
+where
+    Self: Sized,
+{
+    /// Sets a minimum number of values.
+    fn min_len(self, min: usize) -> Self {
+        self.add_test(move |x: &Vec<T>| x.len() >= min)
+    }
+    /// Sets a minimum number of values with custom error message.
+    fn min_len_err(self, min: usize, err: impl ToString) -> Self {
+        self.add_err_test(move |x: &Vec<T>| x.len() >= min, err)
+    }
+    /// Sets a maximum number of values.
+    fn max_len(self, max: usize) -> Self {
+        self.add_test(move |x: &Vec<T>| x.len() <= max)
+    }
+    /// Sets a maximum number of values with custom error message.
+    fn max_len_err(self, max: usize, err: impl ToString) -> Self {
+        self.add_err_test(move |x: &Vec<T>| x.len() <= max, err)
+    }
+    /// Ensures that no value appears more than once.
+    fn unique(self) -> Self
+    where
+        T: PartialEq,
+    {
+        self.add_test(|x: &Vec<T>| is_unique(x))
+    }
+    /// Ensures that no value appears more than once with custom error message.
+    fn unique_err(self, err: impl ToString) -> Self
+    where
+        T: PartialEq,
+    {
+        self.add_err_test(|x: &Vec<T>| is_unique(x), err)
+    }
+    /// Ensures that values are in ascending order.
+    fn sorted(self) -> Self
+    where
+        T: PartialOrd,
+    {
+        self.add_test(|x: &Vec<T>| x.windows(2).all(|w| w[0] <= w[1]))
+    }
+    /// Ensures that values are in ascending order with custom error message.
+    fn sorted_err(self, err: impl ToString) -> Self
+    where
+        T: PartialOrd,
+    {
+        self.add_err_test(|x: &Vec<T>| x.windows(2).all(|w| w[0] <= w[1]), err)
+    }
+}
+
+// Splits a line into the values read by `.list()`, `.array()` and `tuple()`. Values are
+// trimmed by their builder, so whitespace around other separators is not needed.
+pub(crate) fn split_values<'a>(input: &'a str, separator: &str) -> Vec<&'a str> {
+    let separator = separator.trim();
+    if separator.is_empty() {
+        input.split_whitespace().collect()
+    } else if input.is_empty() {
+        Vec::new()
+    } else {
+        input.split(separator).collect()
+    }
+}
+
+// Error message for the value at index `i` of a line.
+pub(crate) fn value_err(i: usize, value: &str, message: &str) -> String {
+    format!("Value {} ({}): {message}", i + 1, value.trim())
+}
+
+// Error message for a line with the wrong number of values.
+pub(crate) fn count_err(expected: usize, found: usize) -> String {
+    format!("Please input {expected} values, not {found}.")
+}
+
+// Inserts `value` before the punctuation and whitespace at the end of `msg`, so `Port: `
+// becomes `Port [8080]: `.
+fn insert_before_suffix(msg: &str, value: &str) -> String {
+    let body = msg.trim_end_matches(|c: char| c.is_whitespace() || ":?>=".contains(c));
+    let (body, suffix) = msg.split_at(body.len());
+    if body.is_empty() {
+        // Nothing comes before the value so spacing meant to separate it is dropped.
+        return format!("{}{suffix}", value.trim_start());
+    }
+    format!("{body}{value}{suffix}")
+}
+
+fn is_unique<T: PartialEq>(list: &[T]) -> bool {
+    list.iter()
+        .enumerate()
+        .all(|(i, x)| !list[i + 1..].contains(x))
+}
+
 #[derive(Clone)]
 pub(crate) struct Prompt {
     pub msg: String,
     pub repeat: bool,
 }
 
+// Why the parsing stage of a builder rejected input.
+pub(crate) enum Rejection<E> {
+    // Parsing failed. The error is passed to `.err_match()`.
+    Parse(E),
+    // A test or `.and_then()` failed. `None` prints the fallback error message.
+    Msg(Option<String>),
+}
+
+// Runs on the raw line, with surrounding whitespace removed, before it is parsed.
+#[derive(Clone)]
+pub(crate) enum RawStep {
+    // Made by `.add_raw_check()`.
+    Check(CheckFn<str>),
+    // Made by `.normalize()`.
+    Normalize(NormalizeFn),
+}
+
 #[derive(Clone)]
-pub(crate) struct Test<T> {
-    pub func: Rc<dyn Fn(&T) -> bool>,
-    pub err: Option<String>,
+pub(crate) enum Test<T> {
+    // Made by `.add_test()`, `.inside()` and their `_err` variants. Prints the fallback
+    // error message when `err` is `None`.
+    Pass {
+        func: TestFunc<T>,
+        err: Option<String>,
+    },
+    // Made by `.add_check()`.
+    Check(CheckFn<T>),
+}
+
+impl<T> Test<T> {
+    // `Err(None)` means the fallback error message should be printed.
+    pub(crate) fn run(&self, value: &T) -> Result<(), Option<String>> {
+        match self {
+            Self::Pass { func, err } => {
+                if func(value) {
+                    Ok(())
+                } else {
+                    Err(err.clone())
+                }
+            }
+            Self::Check(func) => func(value).map_err(Some),
+        }
+    }
 }
 
 /// 'builder' used to store the settings that are used to fetch input.
@@ -112,30 +362,61 @@ pub(crate) struct Test<T> {
 /// `.get()` method only takes these settings by reference so can be called multiple times.
 ///
 /// This type does not have support for default input value.
-pub struct InputBuilder<T: FromStr> {
+///
+/// `E` is the error type produced when parsing input fails.
+pub struct InputBuilder<T, E = <T as FromStr>::Err> {
     msg: Prompt,
+    // Renders the prompt from the message and the default value.
+    prompt_fmt: Option<PromptFn<T>>,
+    // Computes the default value each time input is requested. Set by `.default_with()`.
+    default_with: Option<DefaultFn<T>>,
+    // Renders the default for the prompt. Set by `.show_default()`.
+    show_default: Option<DisplayFn<T>>,
     err: String,
+    raw_steps: Vec<RawStep>,
     tests: Vec<Test<T>>,
-    err_match: Rc<dyn Fn(&T::Err) -> Option<String>>,
-    prompt_output: RefCell<Box<dyn Write>>,
+    parse: Parser<T, E>,
+    err_match: ErrMatch<E>,
+    prompt_output: PromptOutput,
+    // `None` means stdin. Shared with clones of the builder.
+    input_source: Option<Shared<InputSource>>,
+    eof_default: bool,
+    attempts: Option<usize>,
+    echo: Echo,
+    // Prompt for the second input made by `.confirm_password()`.
+    confirm: Option<String>,
+    timeout: Option<Duration>,
+    countdown: bool,
+    cancel_on_interrupt: bool,
+    cancel_word: Option<String>,
+    #[cfg(feature = "tokio")]
+    async_tests: Vec<AsyncTestFn<T>>,
 }
 
 impl<T: FromStr> InputBuilder<T> {
     /// Creates a new instance of `InputBuilder` with default settings.
     pub fn new() -> Self {
-        Self {
-            msg: Prompt {
-                msg: String::new(),
-                repeat: false,
-            },
-            err: DEFAULT_ERR.to_string(),
-            tests: Vec::new(),
-            err_match: Rc::new(|_| None),
-            prompt_output: RefCell::new(Box::new(std::io::stdout())),
-        }
+        Self::from_parser(Shared::new(|input| {
+            T::from_str(input).map_err(Rejection::Parse)
+        }))
+    }
+}
+
+impl<T, E> InputBuilder<T, E> {
+    /// Creates a new instance of `InputBuilder` that parses input with `parser` instead of
+    /// `from_str()`. Errors returned by `parser` are passed to `.err_match()`.
+    pub fn with_parser<F>(parser: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, E> + Shareable + 'static,
+    {
+        Self::from_parser(Shared::new(move |input| {
+            parser(input).map_err(Rejection::Parse)
+        }))
     }
     /// 'gets' the input form the user.
     ///
+    /// # Panics
+    ///
     /// Panics if unable to read input line.
     pub fn get(&self) -> T {
         self.try_get().expect("Failed to read line")
@@ -144,32 +425,328 @@ impl<T: FromStr> InputBuilder<T> {
     ///
     /// # Errors
     ///
-    /// Returns `Err` if unable to read input line.
+    /// Returns `Err` if unable to read input line. Cancelled input fails with
+    /// `io::ErrorKind::Interrupted` and converts back into `ReadInputError::Cancelled`.
     pub fn try_get(&self) -> io::Result<T> {
-        read_input::<T>(
-            &self.msg,
-            &self.err,
-            None,
-            &self.tests,
-            &*self.err_match,
-            &mut (*self.prompt_output.borrow_mut()),
-        )
+        self.try_read().map_err(io::Error::from)
+    }
+    /// 'gets' the input form the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ReadInputError` describing why input could not be read.
+    pub fn try_read(&self) -> Result<T, ReadInputError> {
+        self.read_with_default(None)
     }
     /// Changes or adds a default input value.
-    pub fn default(self, default: T) -> InputBuilderOnce<T> {
+    pub fn default(self, default: T) -> InputBuilderOnce<T, E> {
         InputBuilderOnce {
             builder: self,
             default: Some(default),
+            checked: false,
+        }
+    }
+    /// Like `.default()` but checks `default` against the tests added to the builder
+    /// first, so a default that would be rejected if it was typed can't be used.
+    ///
+    /// Tests added to the returned builder are checked when input is requested, which fails
+    /// with `ReadInputError::InvalidDefault` if the default does not pass them. Only tests added since the last `.map()`,
+    /// `.and_then()` or `.list()` are checked. Async tests and checks on the raw input line
+    /// are not run.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error message of the first test `default` fails.
+    pub fn try_default(self, default: T) -> Result<InputBuilderOnce<T, E>, String> {
+        self.test_value(&default)?;
+        Ok(InputBuilderOnce {
+            checked: true,
+            ..self.default(default)
+        })
+    }
+    /// Computes a default input value with `default` each time input is requested. Unlike
+    /// `.default()` the builder can still be used more than once.
+    ///
+    /// The default is checked against tests like `.try_default()` when it is computed.
+    /// Input fails with `ReadInputError::InvalidDefault` if it does not pass them.
+    ///
+    /// The default is removed by `.map()`, `.and_then()` and `.list()`, so it should be
+    /// set after them.
+    pub fn default_with<F>(mut self, default: F) -> Self
+    where
+        F: Fn() -> T + Shareable + 'static,
+    {
+        self.default_with = Some(Shared::new(default));
+        self
+    }
+    /// Shows the default value set by `.default()` or `.default_with()` in the prompt. The
+    /// prompt `Port: ` with a default of `8080` becomes `Port [8080]: `.
+    ///
+    /// Builders made by `shortcut::confirm()` already show the default in their `[Y/n]`
+    /// hint, so this should not be used with them.
+    ///
+    /// Like `.default_with()` this is removed by `.map()`, `.and_then()` and `.list()`, so
+    /// it should be set after them.
+    pub fn show_default(self) -> Self
+    where
+        T: Display + 'static,
+    {
+        self.show_default_as(DEFAULT_TEMPLATE)
+    }
+    /// Like `.show_default()` but the default is shown using `template`, where `{}` is
+    /// replaced by the default value. The result is placed before any `:`, `?`, `>` or `=`
+    /// and whitespace at the end of the prompt. It replaces any template set before and is
+    /// not shown when the prompt message is empty.
+    ///
+    /// The template is removed by `.map()`, `.and_then()` and `.list()`, so it should be
+    /// set after them.
+    pub fn show_default_as(mut self, template: impl ToString) -> Self
+    where
+        T: Display + 'static,
+    {
+        let template = template.to_string();
+        self.show_default = Some(Shared::new(move |default: &T| {
+            template.replace("{}", &default.to_string())
+        }));
+        self
+    }
+    /// Changes the output type by applying a function to input that has passed all tests.
+    ///
+    /// Tests added after `.map()` check the new output type.
+    pub fn map<U, F>(self, f: F) -> InputBuilder<U, E>
+    where
+        T: 'static,
+        E: 'static,
+        F: Fn(T) -> U + Shareable + 'static,
+    {
+        self.and_then(move |x| Ok(f(x)))
+    }
+    /// Like `.map()` but the function can reject input by returning an error message.
+    /// The message is printed and input is requested again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if async tests have been added with `.add_async_test()`.
+    pub fn and_then<U, F>(self, f: F) -> InputBuilder<U, E>
+    where
+        T: 'static,
+        E: 'static,
+        F: Fn(T) -> Result<U, String> + Shareable + 'static,
+    {
+        #[cfg(feature = "tokio")]
+        assert!(
+            self.async_tests.is_empty(),
+            "Async tests must be added after `.map()` and `.and_then()`"
+        );
+        let prompt_fmt = self.prompt_fmt_for();
+        let parse = self.parse;
+        let tests = self.tests;
+        InputBuilder {
+            msg: self.msg,
+            prompt_fmt,
+            default_with: None,
+            show_default: None,
+            err: self.err,
+            raw_steps: self.raw_steps,
+            tests: Vec::new(),
+            parse: Shared::new(move |input| {
+                let value = parse(input)?;
+                for test in &tests {
+                    test.run(&value).map_err(Rejection::Msg)?;
+                }
+                f(value).map_err(|e| Rejection::Msg(Some(e)))
+            }),
+            err_match: self.err_match,
+            prompt_output: self.prompt_output,
+            input_source: self.input_source,
+            eof_default: self.eof_default,
+            attempts: self.attempts,
+            echo: self.echo,
+            confirm: self.confirm,
+            timeout: self.timeout,
+            countdown: self.countdown,
+            cancel_on_interrupt: self.cancel_on_interrupt,
+            cancel_word: self.cancel_word,
+            #[cfg(feature = "tokio")]
+            async_tests: Vec::new(),
+        }
+    }
+    /// Changes the builder into a builder for a list of values on one line separated by
+    /// `separator`. A separator made of whitespace splits on any amount of whitespace.
+    /// Whitespace around any other separator is ignored, so `", "` also splits `1,2`.
+    ///
+    /// Each value is checked with the settings of this builder and the error message
+    /// says which value failed. Tests added after `.list()` check the whole list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if async tests have been added with `.add_async_test()`.
+    pub fn list(mut self, separator: impl ToString) -> InputBuilder<Vec<T>, E>
+    where
+        T: 'static,
+        E: 'static,
+    {
+        #[cfg(feature = "tokio")]
+        assert!(
+            self.async_tests.is_empty(),
+            "Async tests must be added after `.list()`"
+        );
+        let separator = separator.to_string();
+        let prompt_output = std::mem::replace(
+            &mut self.prompt_output,
+            PromptOutput::new(Box::new(io::sink())),
+        );
+        let input_source = self.input_source.take();
+        InputBuilder {
+            msg: self.msg.clone(),
+            prompt_fmt: self.prompt_fmt_for(),
+            default_with: None,
+            show_default: None,
+            err: self.err.clone(),
+            raw_steps: Vec::new(),
+            tests: Vec::new(),
+            err_match: self.err_match.clone(),
+            prompt_output,
+            input_source,
+            eof_default: self.eof_default,
+            attempts: self.attempts,
+            echo: self.echo,
+            confirm: self.confirm.take(),
+            timeout: self.timeout,
+            countdown: self.countdown,
+            cancel_on_interrupt: self.cancel_on_interrupt,
+            cancel_word: self.cancel_word.clone(),
+            #[cfg(feature = "tokio")]
+            async_tests: Vec::new(),
+            parse: Shared::new(move |input| {
+                split_values(input, &separator)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, value)| {
+                        parse_input(value.to_string(), &self)
+                            .map_err(|message| Rejection::Msg(Some(value_err(i, value, &message))))
+                    })
+                    .collect()
+            }),
+        }
+    }
+    /// Changes the builder into a builder for exactly `N` values on one line separated by
+    /// `separator`. Works like `.list()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if async tests have been added with `.add_async_test()`.
+    pub fn array<const N: usize>(self, separator: impl ToString) -> InputBuilder<[T; N], E>
+    where
+        T: 'static,
+        E: 'static,
+    {
+        self.list(separator).and_then(|values: Vec<T>| {
+            let found = values.len();
+            values.try_into().map_err(|_| count_err(N, found))
+        })
+    }
+    // Internal function for creating a builder with default settings.
+    fn from_parser(parse: Parser<T, E>) -> Self {
+        Self {
+            msg: Prompt {
+                msg: String::new(),
+                repeat: false,
+            },
+            prompt_fmt: None,
+            default_with: None,
+            show_default: None,
+            err: DEFAULT_ERR.to_string(),
+            raw_steps: Vec::new(),
+            tests: Vec::new(),
+            parse,
+            err_match: Shared::new(|_| None),
+            prompt_output: PromptOutput::new(Box::new(std::io::stdout())),
+            input_source: None,
+            eof_default: true,
+            attempts: None,
+            echo: Echo::Visible,
+            confirm: None,
+            timeout: None,
+            countdown: false,
+            cancel_on_interrupt: false,
+            cancel_word: None,
+            #[cfg(feature = "tokio")]
+            async_tests: Vec::new(),
+        }
+    }
+    // Internal function shared by `InputBuilder` and `InputBuilderOnce` for fetching input.
+    fn read_with_default(&self, default: Option<T>) -> Result<T, ReadInputError> {
+        let prompt_output = &mut **lock(&self.prompt_output);
+        if let Some(input_source) = &self.input_source {
+            return self.read_on(
+                default,
+                &mut Terminal {
+                    input_source: &mut **lock(input_source),
+                    prompt_output,
+                    echo: Echo::Visible,
+                    timeout: None,
+                    countdown: false,
+                    cancel_on_interrupt: false,
+                    column: 0,
+                },
+            );
         }
+        // Echo, time limits and catching Ctrl-C need a terminal.
+        let terminal = io::stdin().is_terminal();
+        self.read_on(
+            default,
+            &mut Terminal {
+                input_source: &mut io::stdin().lock(),
+                prompt_output,
+                echo: if terminal { self.echo } else { Echo::Visible },
+                timeout: self.timeout.filter(|_| terminal),
+                countdown: self.countdown,
+                cancel_on_interrupt: self.cancel_on_interrupt && terminal,
+                column: 0,
+            },
+        )
+    }
+    // Internal function for showing extra information such as the default in the prompt.
+    fn prompt_with<F>(mut self, prompt_fmt: F) -> Self
+    where
+        F: Fn(&str, Option<&T>) -> String + Shareable + 'static,
+    {
+        self.prompt_fmt = Some(Shared::new(prompt_fmt));
+        self
+    }
+    // Internal function for checking a value that was not typed, such as a default.
+    fn test_value(&self, value: &T) -> Result<(), String> {
+        for test in &self.tests {
+            test.run(value)
+                .map_err(|e| e.unwrap_or_else(|| self.err.clone()))?;
+        }
+        Ok(())
+    }
+    // Internal function for keeping the prompt when the output type changes. The default
+    // has the new type so it is not passed on.
+    fn prompt_fmt_for<U>(&self) -> Option<PromptFn<U>>
+    where
+        T: 'static,
+    {
+        let prompt_fmt = self.prompt_fmt.clone()?;
+        let prompt_fmt: PromptFn<U> = Shared::new(move |msg, _| prompt_fmt(msg, None));
+        Some(prompt_fmt)
+    }
+    // Internal function for fetching input through any `Console`.
+    fn read_on(&self, default: Option<T>, console: &mut dyn Console) -> Result<T, ReadInputError> {
+        read_input(self, default, console)
     }
     // Internal function for adding tests and constraints.
-    fn test_err_opt(mut self, func: Rc<dyn Fn(&T) -> bool>, err: Option<String>) -> Self {
-        self.tests.push(Test { func, err });
+    fn test_err_opt(mut self, func: TestFunc<T>, err: Option<String>) -> Self {
+        self.tests.push(Test::Pass { func, err });
         self
     }
 }
 
-impl<T: FromStr> InputBuild<T> for InputBuilder<T> {
+impl<T, E> InputBuild<T> for InputBuilder<T, E> {
+    type ParseErr = E;
+
     fn msg(mut self, msg: impl ToString) -> Self {
         self.msg = Prompt {
             msg: msg.to_string(),
@@ -189,24 +766,47 @@ impl<T: FromStr> InputBuild<T> for InputBuilder<T> {
         self
     }
 
-    fn add_test<F: Fn(&T) -> bool + 'static>(self, test: F) -> Self {
-        self.test_err_opt(Rc::new(test), None)
+    fn add_test<F: Fn(&T) -> bool + Shareable + 'static>(self, test: F) -> Self {
+        self.test_err_opt(Shared::new(test), None)
     }
     fn add_err_test<F>(self, test: F, err: impl ToString) -> Self
     where
-        F: Fn(&T) -> bool + 'static,
+        F: Fn(&T) -> bool + Shareable + 'static,
+    {
+        self.test_err_opt(Shared::new(test), Some(err.to_string()))
+    }
+    fn add_check<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), String> + Shareable + 'static,
     {
-        self.test_err_opt(Rc::new(test), Some(err.to_string()))
+        self.tests.push(Test::Check(Shared::new(check)));
+        self
+    }
+    fn add_raw_check<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Shareable + 'static,
+    {
+        self.raw_steps.push(RawStep::Check(Shared::new(check)));
+        self
+    }
+    fn normalize<F: Fn(&str) -> String + Shareable + 'static>(mut self, normalize: F) -> Self {
+        self.raw_steps
+            .push(RawStep::Normalize(Shared::new(normalize)));
+        self
     }
     fn clear_tests(mut self) -> Self {
         self.tests = Vec::new();
+        #[cfg(feature = "tokio")]
+        {
+            self.async_tests = Vec::new();
+        }
         self
     }
     fn err_match<F>(mut self, err_match: F) -> Self
     where
-        F: Fn(&T::Err) -> Option<String> + 'static,
+        F: Fn(&E) -> Option<String> + Shareable + 'static,
     {
-        self.err_match = Rc::new(err_match);
+        self.err_match = Shared::new(err_match);
         self
     }
     fn inside<U: InsideFunc<T>>(self, constraint: U) -> Self {
@@ -220,17 +820,69 @@ impl<T: FromStr> InputBuild<T> for InputBuilder<T> {
         self
     }
 
-    fn prompting_on(mut self, prompt_output: RefCell<Box<dyn Write>>) -> Self {
-        self.prompt_output = prompt_output;
+    fn prompting_on(mut self, prompt_output: impl Write + Sendable + 'static) -> Self {
+        self.prompt_output = PromptOutput::new(Box::new(prompt_output));
         self
     }
 
     fn prompting_on_stderr(self) -> Self {
-        self.prompting_on(RefCell::new(Box::new(std::io::stderr())))
+        self.prompting_on(std::io::stderr())
+    }
+
+    fn reading_from(mut self, input_source: impl BufRead + Sendable + 'static) -> Self {
+        self.input_source = Some(Shared::new(InputSource::new(Box::new(input_source))));
+        self
+    }
+
+    fn eof_default(mut self, use_default: bool) -> Self {
+        self.eof_default = use_default;
+        self
+    }
+
+    fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = Some(attempts);
+        self
+    }
+
+    fn hidden(mut self) -> Self {
+        self.echo = Echo::Hidden;
+        self
+    }
+
+    fn masked(mut self, mask: char) -> Self {
+        self.echo = Echo::Masked(mask);
+        self
+    }
+
+    fn confirm_password(mut self, msg: impl ToString) -> Self {
+        self.confirm = Some(msg.to_string());
+        self
+    }
+
+    fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn countdown(mut self) -> Self {
+        self.countdown = true;
+        self
+    }
+
+    fn cancel_on_interrupt(mut self) -> Self {
+        self.cancel_on_interrupt = true;
+        self
+    }
+
+    fn cancel_word(mut self, word: impl ToString) -> Self {
+        self.cancel_word = Some(word.to_string());
+        self
     }
 }
 
-impl<T: FromStr + PartialOrd + 'static> InputConstraints<T> for InputBuilder<T> {}
+impl<T: PartialOrd + Shareable + 'static, E> InputConstraints<T> for InputBuilder<T, E> {}
+
+impl<T, E> ListConstraints<T> for InputBuilder<Vec<T>, E> {}
 
 impl<T: FromStr> Default for InputBuilder<T> {
     fn default() -> Self {
@@ -238,14 +890,30 @@ impl<T: FromStr> Default for InputBuilder<T> {
     }
 }
 
-impl<T: FromStr + Clone> Clone for InputBuilder<T> {
+impl<T: Clone, E> Clone for InputBuilder<T, E> {
     fn clone(&self) -> Self {
         Self {
             msg: self.msg.clone(),
+            prompt_fmt: self.prompt_fmt.clone(),
+            default_with: self.default_with.clone(),
+            show_default: self.show_default.clone(),
             err: self.err.clone(),
+            raw_steps: self.raw_steps.clone(),
             tests: self.tests.clone(),
+            parse: self.parse.clone(),
             err_match: self.err_match.clone(),
-            prompt_output: RefCell::new(Box::new(std::io::stdout())),
+            prompt_output: PromptOutput::new(Box::new(std::io::stdout())),
+            input_source: self.input_source.clone(),
+            eof_default: self.eof_default,
+            attempts: self.attempts,
+            echo: self.echo,
+            confirm: self.confirm.clone(),
+            timeout: self.timeout,
+            countdown: self.countdown,
+            cancel_on_interrupt: self.cancel_on_interrupt,
+            cancel_word: self.cancel_word.clone(),
+            #[cfg(feature = "tokio")]
+            async_tests: self.async_tests.clone(),
         }
     }
 }
@@ -255,14 +923,18 @@ impl<T: FromStr + Clone> Clone for InputBuilder<T> {
 /// `.get()` method takes ownership of the settings so can be called only once without cloning.
 ///
 /// This type has support for default input value.
-pub struct InputBuilderOnce<T: FromStr> {
-    builder: InputBuilder<T>,
+pub struct InputBuilderOnce<T, E = <T as FromStr>::Err> {
+    builder: InputBuilder<T, E>,
     default: Option<T>,
+    // Set by `.try_default()`. The default is checked again in case tests were added.
+    checked: bool,
 }
 
-impl<T: FromStr> InputBuilderOnce<T> {
+impl<T, E> InputBuilderOnce<T, E> {
     /// 'gets' the input form the user.
     ///
+    /// # Panics
+    ///
     /// Panics if unable to read input line.
     pub fn get(self) -> T {
         self.try_get().expect("Failed to read line")
@@ -271,21 +943,54 @@ impl<T: FromStr> InputBuilderOnce<T> {
     ///
     /// # Errors
     ///
-    /// Returns `Err` if unable to read input line.
+    /// Returns `Err` if unable to read input line. Cancelled input fails with
+    /// `io::ErrorKind::Interrupted` and converts back into `ReadInputError::Cancelled`.
     pub fn try_get(self) -> io::Result<T> {
-        read_input::<T>(
-            &self.builder.msg,
-            &self.builder.err,
-            self.default,
-            &self.builder.tests,
-            &*self.builder.err_match,
-            &mut (*self.builder.prompt_output.borrow_mut()),
-        )
+        self.try_read().map_err(io::Error::from)
+    }
+    /// 'gets' the input form the user.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ReadInputError` describing why input could not be read.
+    pub fn try_read(self) -> Result<T, ReadInputError> {
+        let (builder, default) = self.into_parts()?;
+        builder.read_with_default(default)
+    }
+    /// Shows the default value in the prompt. See `InputBuilder::show_default`.
+    pub fn show_default(self) -> Self
+    where
+        T: Display + 'static,
+    {
+        self.internal(InputBuilder::show_default)
+    }
+    /// Shows the default value in the prompt using `template`. See
+    /// `InputBuilder::show_default_as`.
+    pub fn show_default_as(self, template: impl ToString) -> Self
+    where
+        T: Display + 'static,
+    {
+        self.internal(|x| x.show_default_as(template))
+    }
+    // Internal function for fetching input. Checks a default from `.try_default()` against
+    // tests added after it.
+    fn into_parts(self) -> Result<(InputBuilder<T, E>, Option<T>), ReadInputError> {
+        let Self {
+            builder,
+            default,
+            checked,
+        } = self;
+        if let (true, Some(default)) = (checked, &default) {
+            builder
+                .test_value(default)
+                .map_err(ReadInputError::InvalidDefault)?;
+        }
+        Ok((builder, default))
     }
     // Function that makes it less verbose to change settings of internal `InputBuilder`.
     fn internal<F>(self, with: F) -> Self
     where
-        F: FnOnce(InputBuilder<T>) -> InputBuilder<T>,
+        F: FnOnce(InputBuilder<T, E>) -> InputBuilder<T, E>,
     {
         Self {
             builder: with(self.builder),
@@ -294,7 +999,9 @@ impl<T: FromStr> InputBuilderOnce<T> {
     }
 }
 
-impl<T: FromStr> InputBuild<T> for InputBuilderOnce<T> {
+impl<T, E> InputBuild<T> for InputBuilderOnce<T, E> {
+    type ParseErr = E;
+
     fn msg(self, msg: impl ToString) -> Self {
         self.internal(|x| x.msg(msg))
     }
@@ -304,21 +1011,36 @@ impl<T: FromStr> InputBuild<T> for InputBuilderOnce<T> {
     fn err(self, err: impl ToString) -> Self {
         self.internal(|x| x.err(err))
     }
-    fn add_test<F: Fn(&T) -> bool + 'static>(self, test: F) -> Self {
+    fn add_test<F: Fn(&T) -> bool + Shareable + 'static>(self, test: F) -> Self {
         self.internal(|x| x.add_test(test))
     }
     fn add_err_test<F>(self, test: F, err: impl ToString) -> Self
     where
-        F: Fn(&T) -> bool + 'static,
+        F: Fn(&T) -> bool + Shareable + 'static,
     {
         self.internal(|x| x.add_err_test(test, err))
     }
+    fn add_check<F>(self, check: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), String> + Shareable + 'static,
+    {
+        self.internal(|x| x.add_check(check))
+    }
+    fn add_raw_check<F>(self, check: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Shareable + 'static,
+    {
+        self.internal(|x| x.add_raw_check(check))
+    }
+    fn normalize<F: Fn(&str) -> String + Shareable + 'static>(self, normalize: F) -> Self {
+        self.internal(|x| x.normalize(normalize))
+    }
     fn clear_tests(self) -> Self {
         self.internal(InputBuild::clear_tests)
     }
     fn err_match<F>(self, err_match: F) -> Self
     where
-        F: Fn(&T::Err) -> Option<String> + 'static,
+        F: Fn(&E) -> Option<String> + Shareable + 'static,
     {
         self.internal(|x| x.err_match(err_match))
     }
@@ -332,25 +1054,65 @@ impl<T: FromStr> InputBuild<T> for InputBuilderOnce<T> {
         self.internal(InputBuild::toggle_msg_repeat)
     }
 
-    fn prompting_on(self, prompt_output: RefCell<Box<dyn Write>>) -> Self {
+    fn prompting_on(self, prompt_output: impl Write + Sendable + 'static) -> Self {
         self.internal(|x| x.prompting_on(prompt_output))
     }
 
     fn prompting_on_stderr(self) -> Self {
-        self.internal(|x| x.prompting_on(RefCell::new(Box::new(std::io::stderr()))))
+        self.internal(InputBuild::prompting_on_stderr)
+    }
+
+    fn reading_from(self, input_source: impl BufRead + Sendable + 'static) -> Self {
+        self.internal(|x| x.reading_from(input_source))
+    }
+
+    fn eof_default(self, use_default: bool) -> Self {
+        self.internal(|x| x.eof_default(use_default))
+    }
+
+    fn attempts(self, attempts: usize) -> Self {
+        self.internal(|x| x.attempts(attempts))
+    }
+
+    fn hidden(self) -> Self {
+        self.internal(InputBuild::hidden)
+    }
+
+    fn masked(self, mask: char) -> Self {
+        self.internal(|x| x.masked(mask))
+    }
+
+    fn confirm_password(self, msg: impl ToString) -> Self {
+        self.internal(|x| x.confirm_password(msg))
+    }
+
+    fn timeout(self, timeout: Duration) -> Self {
+        self.internal(|x| x.timeout(timeout))
+    }
+
+    fn countdown(self) -> Self {
+        self.internal(InputBuild::countdown)
+    }
+
+    fn cancel_on_interrupt(self) -> Self {
+        self.internal(InputBuild::cancel_on_interrupt)
+    }
+
+    fn cancel_word(self, word: impl ToString) -> Self {
+        self.internal(|x| x.cancel_word(word))
     }
 }
 
-impl<T: FromStr + PartialOrd + 'static> InputConstraints<T> for InputBuilderOnce<T> {}
+impl<T: PartialOrd + Shareable + 'static, E> InputConstraints<T> for InputBuilderOnce<T, E> {}
 
-impl<T> Clone for InputBuilderOnce<T>
-where
-    T: Clone + FromStr,
-{
+impl<T, E> ListConstraints<T> for InputBuilderOnce<Vec<T>, E> {}
+
+impl<T: Clone, E> Clone for InputBuilderOnce<T, E> {
     fn clone(&self) -> Self {
         Self {
             default: self.default.clone(),
             builder: self.builder.clone(),
+            checked: self.checked,
         }
     }
 }
@@ -1,3 +1,7 @@
 //! Module to be imported in the style `use read_input::prelude::*;` to get the essential functions and traits.
 
-pub use crate::{shortcut::input, InputBuild, InputConstraints};
+pub use crate::{
+    input,
+    shortcut::{input, input_with},
+    InputBuild, InputConstraints, ListConstraints,
+};
@@ -0,0 +1,137 @@
+use crate::ReadInputError;
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, StdinLock},
+    str::FromStr,
+};
+
+/// Reads whitespace separated values quickly. Made for reading large amounts of input
+/// such as in competitive programming.
+///
+/// Unlike `InputBuilder` there are no prompts, tests or retries. Stdin is locked once
+/// when the scanner is created and lines are read into a single reused buffer.
+pub struct Scanner<R> {
+    input_source: R,
+    buffer: String,
+    pos: usize,
+}
+
+impl Scanner<StdinLock<'static>> {
+    /// Creates a `Scanner` that reads from stdin. Stdin stays locked until the scanner is
+    /// dropped.
+    pub fn stdin() -> Self {
+        Self::new(io::stdin().lock())
+    }
+}
+
+impl<R: BufRead> Scanner<R> {
+    /// Creates a `Scanner` that reads from any `BufRead` source.
+    pub fn new(input_source: R) -> Self {
+        Self {
+            input_source,
+            buffer: String::new(),
+            pos: 0,
+        }
+    }
+    /// Returns the next value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the end of input is reached, input can't be read or the value can't be
+    /// parsed.
+    pub fn get<T>(&mut self) -> T
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        self.try_get().expect("Failed to scan value")
+    }
+    /// Returns the next `n` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the end of input is reached, input can't be read or a value can't be
+    /// parsed.
+    pub fn get_vec<T>(&mut self, n: usize) -> Vec<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Debug,
+    {
+        (0..n).map(|_| self.get()).collect()
+    }
+    /// Returns the next value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ScanError` if the end of input is reached, input can't be read or the
+    /// value can't be parsed.
+    pub fn try_get<T: FromStr>(&mut self) -> Result<T, ScanError<T::Err>> {
+        let token = self.token()?.ok_or(ScanError::Read(ReadInputError::Eof))?;
+        token.parse().map_err(|error| ScanError::Parse {
+            token: token.to_string(),
+            error,
+        })
+    }
+    /// Returns the next whitespace separated token or `None` at the end of input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if input can't be read.
+    pub fn token(&mut self) -> io::Result<Option<&str>> {
+        loop {
+            let rest = &self.buffer[self.pos..];
+            if let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+                let start = self.pos + start;
+                let end = self.buffer[start..]
+                    .find(char::is_whitespace)
+                    .map_or(self.buffer.len(), |end| start + end);
+                self.pos = end;
+                return Ok(Some(&self.buffer[start..end]));
+            }
+            self.buffer.clear();
+            self.pos = 0;
+            if self.input_source.read_line(&mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+/// Reasons `Scanner::try_get` can fail.
+#[derive(Debug)]
+pub enum ScanError<E> {
+    /// Input could not be read or the end of input was reached.
+    Read(ReadInputError),
+    /// A token could not be parsed.
+    Parse {
+        /// The token that could not be parsed.
+        token: String,
+        /// The error produced by `from_str()`.
+        error: E,
+    },
+}
+
+impl<E> From<io::Error> for ScanError<E> {
+    fn from(error: io::Error) -> Self {
+        Self::Read(error.into())
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ScanError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => e.fmt(f),
+            Self::Parse { token, error } => write!(f, "failed to parse \"{token}\": {error}"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ScanError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Read(e) => Some(e),
+            Self::Parse { error, .. } => Some(error),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// Types used to store closures, writers and readers inside builders. With the `sync`
+// feature these are `Arc` and `Mutex` based so builders are `Send + Sync`.
+
+pub use self::storage::*;
+
+// Result of an async test. `Err(None)` means the fallback error message is printed.
+#[cfg(feature = "tokio")]
+pub(crate) type TestResult = Result<(), Option<String>>;
+
+#[cfg(not(feature = "sync"))]
+mod storage {
+    #[cfg(feature = "tokio")]
+    use super::TestResult;
+    use crate::Rejection;
+    use std::{
+        cell::{RefCell, RefMut},
+        io::{BufRead, Write},
+        rc::Rc,
+    };
+    #[cfg(feature = "tokio")]
+    use std::{future::Future, pin::Pin};
+
+    /// Bound on closures and values stored in builders.
+    ///
+    /// Implemented for every type. With the `sync` feature enabled it requires `Send + Sync`.
+    pub trait Shareable {}
+
+    impl<T: ?Sized> Shareable for T {}
+
+    /// Bound on futures, writers and readers stored in builders.
+    ///
+    /// Implemented for every type. With the `sync` feature enabled it requires `Send`.
+    pub trait Sendable {}
+
+    impl<T: ?Sized> Sendable for T {}
+
+    // Writer that prompts are sent to.
+    pub(crate) type PromptOutput = RefCell<Box<dyn Write>>;
+
+    // Reader that input is read from.
+    pub(crate) type InputSource = RefCell<Box<dyn BufRead>>;
+
+    /// Function returned by `InsideFunc::contains_func`.
+    pub type TestFunc<T> = Rc<dyn Fn(&T) -> bool>;
+
+    pub(crate) type Shared<T> = Rc<T>;
+    pub(crate) type CheckFn<T> = Rc<dyn Fn(&T) -> Result<(), String>>;
+    pub(crate) type NormalizeFn = Rc<dyn Fn(&str) -> String>;
+    pub(crate) type Parser<T, E> = Rc<dyn Fn(&str) -> Result<T, Rejection<E>>>;
+    pub(crate) type ErrMatch<E> = Rc<dyn Fn(&E) -> Option<String>>;
+    pub(crate) type PromptFn<T> = Rc<dyn Fn(&str, Option<&T>) -> String>;
+    pub(crate) type DefaultFn<T> = Rc<dyn Fn() -> T>;
+    pub(crate) type DisplayFn<T> = Rc<dyn Fn(&T) -> String>;
+    #[cfg(feature = "tokio")]
+    pub(crate) type AsyncTestFn<T> = Rc<dyn Fn(&T) -> Pin<Box<dyn Future<Output = TestResult>>>>;
+
+    pub(crate) fn lock<T: ?Sized>(cell: &RefCell<T>) -> RefMut<'_, T> {
+        cell.borrow_mut()
+    }
+}
+
+#[cfg(feature = "sync")]
+mod storage {
+    #[cfg(feature = "tokio")]
+    use super::TestResult;
+    use crate::Rejection;
+    #[cfg(feature = "tokio")]
+    use std::{future::Future, pin::Pin};
+    use std::{
+        io::{BufRead, Write},
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+    };
+
+    /// Bound on closures and values stored in builders.
+    ///
+    /// Requires `Send + Sync` because the `sync` feature is enabled.
+    pub trait Shareable: Send + Sync {}
+
+    impl<T: Send + Sync + ?Sized> Shareable for T {}
+
+    /// Bound on futures, writers and readers stored in builders.
+    ///
+    /// Requires `Send` because the `sync` feature is enabled.
+    pub trait Sendable: Send {}
+
+    impl<T: Send + ?Sized> Sendable for T {}
+
+    // Writer that prompts are sent to.
+    pub(crate) type PromptOutput = Mutex<Box<dyn Write + Send>>;
+
+    // Reader that input is read from.
+    pub(crate) type InputSource = Mutex<Box<dyn BufRead + Send>>;
+
+    /// Function returned by `InsideFunc::contains_func`.
+    pub type TestFunc<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+    pub(crate) type Shared<T> = Arc<T>;
+    pub(crate) type CheckFn<T> = Arc<dyn Fn(&T) -> Result<(), String> + Send + Sync>;
+    pub(crate) type NormalizeFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+    pub(crate) type Parser<T, E> = Arc<dyn Fn(&str) -> Result<T, Rejection<E>> + Send + Sync>;
+    pub(crate) type ErrMatch<E> = Arc<dyn Fn(&E) -> Option<String> + Send + Sync>;
+    pub(crate) type PromptFn<T> = Arc<dyn Fn(&str, Option<&T>) -> String + Send + Sync>;
+    pub(crate) type DefaultFn<T> = Arc<dyn Fn() -> T + Send + Sync>;
+    pub(crate) type DisplayFn<T> = Arc<dyn Fn(&T) -> String + Send + Sync>;
+    #[cfg(feature = "tokio")]
+    pub(crate) type AsyncTestFn<T> =
+        Arc<dyn Fn(&T) -> Pin<Box<dyn Future<Output = TestResult> + Send>> + Send + Sync>;
+
+    // A panic while prompting can't leave a writer or reader in a state worse than an
+    // interrupted read, so poisoning is ignored.
+    pub(crate) fn lock<T: ?Sized>(cell: &Mutex<T>) -> MutexGuard<'_, T> {
+        cell.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
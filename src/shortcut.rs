@@ -1,13 +1,16 @@
 //! Collection of functions that make things a little less verbose.
 
-use crate::{test_generators::InsideFunc, InputBuild, InputBuilder};
-use std::{error::Error, fmt::Display, str::FromStr};
+use crate::{
+    test_generators::InsideFunc, CountError, InputBuild, InputBuilder, Shareable, TupleBuild,
+    TupleInput,
+};
+use std::{error::Error, fmt::Display, num::ParseIntError, str::FromStr};
 
 /// Shortcut function. Fetches input that is validated with a test function.
 pub fn valid_input<T, F>(test: F) -> T
 where
     T: FromStr,
-    F: Fn(&T) -> bool + 'static,
+    F: Fn(&T) -> bool + Shareable + 'static,
 {
     input().add_test(test).get()
 }
@@ -31,6 +34,191 @@ pub fn input<T: FromStr>() -> InputBuilder<T> {
     InputBuilder::new()
 }
 
+/// Creates a new instance of `InputBuilder` that asks a yes or no question.
+///
+/// Accepts `y`, `yes`, `n` and `no` in any case. A `[y/n]` hint is added to the prompt
+/// that shows which answer is picked by `.default()`, for example `Delete? [Y/n] `.
+/// Because the hint shows the default, `.show_default()` should not be used as well.
+pub fn confirm(msg: impl ToString) -> InputBuilder<bool, ()> {
+    input_with(|input: &str| match input.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "n" | "no" => Ok(false),
+        _ => Err(()),
+    })
+    .msg(msg)
+    .err("Please type yes or no.")
+    .prompt_with(|msg, default| {
+        let hint = match default {
+            Some(true) => "[Y/n]",
+            Some(false) => "[y/N]",
+            None => "[y/n]",
+        };
+        format!("{msg} {hint} ")
+    })
+}
+
+/// Creates a new instance of `InputBuilder` that prints a numbered list of `options` and
+/// returns the option picked. Options can be picked by number or by typing them out. If an
+/// option looks like a number typing it picks that option rather than the numbered one.
+///
+/// # Panics
+///
+/// Panics if `options` is empty.
+pub fn select<T>(options: Vec<T>) -> InputBuilder<T, ParseIntError>
+where
+    T: Display + Clone + Shareable + 'static,
+{
+    let list = menu_list(&options);
+    menu(&options)
+        .map(move |i| options[i].clone())
+        .prompt_with(move |msg, _| format!("{list}{msg}"))
+}
+
+/// Like `select()` but returns the index of the option picked rather than the option.
+///
+/// # Panics
+///
+/// Panics if `options` is empty.
+pub fn select_index<T: Display>(options: &[T]) -> InputBuilder<usize, ParseIntError> {
+    let list = menu_list(options);
+    menu(options).prompt_with(move |msg, _| format!("{list}{msg}"))
+}
+
+/// Creates a new instance of `InputBuilder` that prints a numbered list of `options` and
+/// returns every option picked. Options are picked by number, separated by commas or
+/// spaces. Ranges such as `1-3` pick every option in between.
+///
+/// Options are returned in the order they are listed. `.min_len()` and `.max_len()` limit
+/// how many options can be picked.
+pub fn multi_select<T>(options: Vec<T>) -> InputBuilder<Vec<T>, ()>
+where
+    T: Display + Clone + Shareable + 'static,
+{
+    let list = menu_list(&options);
+    multi_menu(options.len())
+        .map(move |picked| picked.into_iter().map(|i| options[i].clone()).collect())
+        .prompt_with(move |msg, _| format!("{list}{msg}"))
+}
+
+/// Like `multi_select()` but returns the indexes of the options picked rather than the
+/// options.
+pub fn multi_select_index<T: Display>(options: &[T]) -> InputBuilder<Vec<usize>, ()> {
+    let list = menu_list(options);
+    multi_menu(options.len()).prompt_with(move |msg, _| format!("{list}{msg}"))
+}
+
+// Parses a list of option numbers and ranges into sorted indexes.
+fn multi_menu(len: usize) -> InputBuilder<Vec<usize>, ()> {
+    let number = move |x: &str| x.parse().ok().filter(|x| (1..=len).contains(x));
+    input_with(move |input: &str| {
+        let mut picked = Vec::new();
+        for part in input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+        {
+            let (start, end) = match part.split_once('-') {
+                Some((start, end)) => (number(start), number(end)),
+                None => (number(part), number(part)),
+            };
+            match (start, end) {
+                (Some(start), Some(end)) if start <= end => picked.extend(start - 1..end),
+                _ => return Err(()),
+            }
+        }
+        picked.sort_unstable();
+        picked.dedup();
+        Ok(picked)
+    })
+    .err("Please type the numbers of options separated by commas, such as 1-3,5.")
+}
+
+// Parses the number or name of an option into its index.
+fn menu<T: Display>(options: &[T]) -> InputBuilder<usize, ParseIntError> {
+    assert!(!options.is_empty(), "`select` needs at least one option");
+    let names: Vec<String> = options.iter().map(ToString::to_string).collect();
+    input_with(
+        move |input: &str| match names.iter().position(|name| name == input) {
+            Some(i) => Ok(i + 1),
+            None => input.parse(),
+        },
+    )
+    .inside(1..=options.len())
+    .err("Please type the number or name of an option.")
+    .map(|number| number - 1)
+}
+
+fn menu_list<T: Display>(options: &[T]) -> String {
+    use std::fmt::Write;
+
+    let mut list = String::new();
+    for (i, option) in options.iter().enumerate() {
+        let _ = writeln!(list, "{}) {option}", i + 1);
+    }
+    list
+}
+
+/// Creates a new instance of `InputBuilder` for reading a password. Input is not shown
+/// while it is typed.
+pub fn password() -> InputBuilder<String> {
+    input().hidden()
+}
+
+/// Creates a new instance of `InputBuilder` for a list of values separated by whitespace.
+///
+/// Use `input().list()` to split on something else or to add tests that check each value.
+pub fn input_list<T>() -> InputBuilder<Vec<T>, T::Err>
+where
+    T: FromStr + 'static,
+    T::Err: 'static,
+{
+    input().list(" ")
+}
+
+/// Creates a new instance of `InputBuilder` for a tuple of values on one line separated by
+/// `separator`. Each value is read with its own builder so it can have its own tests and
+/// `.err_match()`. A separator made of whitespace splits on any amount of whitespace.
+/// Whitespace around any other separator is ignored.
+pub fn tuple<B>(builders: B, separator: impl ToString) -> InputBuilder<B::Output, CountError>
+where
+    B: TupleBuild + Shareable + 'static,
+{
+    crate::tuple::tuple(builders, separator.to_string())
+}
+
+/// Creates a new instance of `InputBuilder` for a tuple of values on one line separated by
+/// `separator`, such as `input_tuple::<(i32, i32)>(",")`.
+pub fn input_tuple<T>(separator: impl ToString) -> InputBuilder<T, CountError>
+where
+    T: TupleInput,
+    T::Builders: Shareable + 'static,
+{
+    tuple(T::builders(), separator)
+}
+
+/// Creates a new instance of `InputBuilder` for a line in the format `format`, where each
+/// `{}` is a value. Used by the `input!` macro.
+///
+/// # Panics
+///
+/// Panics if `format` does not have a `{}` for each type or two `{}` are next to each
+/// other.
+pub fn scan<T>(format: &str) -> InputBuilder<T, ()>
+where
+    T: TupleInput,
+    T::Builders: Shareable + 'static,
+{
+    crate::tuple::scan(format)
+}
+
+/// Creates a new instance of `InputBuilder` that parses input with a custom function
+/// rather than `from_str()`.
+pub fn input_with<T, E, F>(parser: F) -> InputBuilder<T, E>
+where
+    F: Fn(&str) -> Result<T, E> + Shareable + 'static,
+{
+    InputBuilder::with_parser(parser)
+}
+
 /// Creates a new instance of `InputBuilder` with settings specifically
 /// tailored to the type you want.
 pub fn input_d<T: DefaultBuilderSettings>() -> InputBuilder<T> {
@@ -103,7 +291,7 @@ impl_default_builder_for_float! { f32, f64 }
 
 /// Produces an error message from an error type. Made for use in `.err_match()`
 pub fn with_display<T: Display>(x: &T) -> Option<String> {
-    Some(format!("Error: \"{}\"", x))
+    Some(format!("Error: \"{x}\""))
 }
 
 #[deprecated(
@@ -1,29 +1,29 @@
+use crate::shared::{Shareable, Shared, TestFunc};
 use std::{
     cmp::PartialOrd,
     ops::{
         Bound::{Excluded, Included, Unbounded},
         Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
     },
-    rc::Rc,
 };
 
 /// This trait is used to describe constraints with different types.
 pub trait InsideFunc<T> {
     /// Returns constraint as a function.
-    fn contains_func(self) -> Rc<dyn Fn(&T) -> bool>;
+    fn contains_func(self) -> TestFunc<T>;
 }
 
-impl<T: PartialEq + 'static> InsideFunc<T> for Vec<T> {
-    fn contains_func(self) -> Rc<dyn Fn(&T) -> bool> {
-        Rc::new(move |x| self.contains(x))
+impl<T: PartialEq + Shareable + 'static> InsideFunc<T> for Vec<T> {
+    fn contains_func(self) -> TestFunc<T> {
+        Shared::new(move |x| self.contains(x))
     }
 }
 
 macro_rules! impl_inside_func_for_arrays {
     ($($e:expr),*) => {$(
-        impl<T: PartialEq + 'static> InsideFunc<T> for [T; $e] {
-            fn contains_func(self) -> Rc<dyn Fn(&T) -> bool> {
-                Rc::new(move |x| self.contains(x))
+        impl<T: PartialEq + Shareable + 'static> InsideFunc<T> for [T; $e] {
+            fn contains_func(self) -> TestFunc<T> {
+                Shared::new(move |x| self.contains(x))
             }
         }
     )*}
@@ -35,19 +35,19 @@ impl_inside_func_for_arrays! {
     29, 30, 31, 32
 }
 
-fn range_contains_func<T, U>(range: U) -> Rc<dyn Fn(&T) -> bool>
+fn range_contains_func<T, U>(range: U) -> TestFunc<T>
 where
     T: PartialOrd,
-    U: RangeBounds<T> + 'static,
+    U: RangeBounds<T> + Shareable + 'static,
 {
-    Rc::new(move |x| {
+    Shared::new(move |x| {
         (match range.start_bound() {
-            Included(ref start) => *start <= x,
-            Excluded(ref start) => *start < x,
+            Included(start) => start <= x,
+            Excluded(start) => start < x,
             Unbounded => true,
         }) && (match range.end_bound() {
-            Included(ref end) => x <= *end,
-            Excluded(ref end) => x < *end,
+            Included(end) => x <= end,
+            Excluded(end) => x < end,
             Unbounded => true,
         })
     })
@@ -55,8 +55,8 @@ where
 
 macro_rules! impl_inside_func_for_ranges {
     ($($t:ty),*) => {$(
-        impl<T: PartialOrd + 'static> InsideFunc<T> for $t {
-            fn contains_func(self) -> Rc<dyn Fn(&T) -> bool> {
+        impl<T: PartialOrd + Shareable + 'static> InsideFunc<T> for $t {
+            fn contains_func(self) -> TestFunc<T> {
                 range_contains_func(self)
             }
         }
@@ -0,0 +1,126 @@
+//! Tools for testing interactive input flows without a terminal.
+//!
+//! The functions in this module drive a builder with a scripted list of input lines and
+//! record everything that would have been shown to the user.
+//!
+//! ```
+//! use read_input::{prelude::*, testing::{run, Event}};
+//!
+//! let builder = input::<u32>().repeat_msg("Age: ").err("Not a number.");
+//! let transcript = run(&builder, &["ten", "10"]);
+//!
+//! assert_eq!(transcript.retries(), 1);
+//! assert_eq!(
+//!     transcript.events,
+//!     vec![
+//!         Event::Prompt("Age: ".to_string()),
+//!         Event::Input("ten".to_string()),
+//!         Event::Error("Not a number.".to_string()),
+//!         Event::Prompt("Age: ".to_string()),
+//!         Event::Input("10".to_string()),
+//!     ]
+//! );
+//! assert_eq!(transcript.result.unwrap(), 10);
+//! ```
+
+use crate::{core::Console, InputBuilder, InputBuilderOnce, ReadInputError};
+use std::io;
+
+/// Something that happened while input was being fetched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A prompt message was printed.
+    Prompt(String),
+    /// A line was read from the script. Does not include the line ending.
+    Input(String),
+    /// An error message was printed because input did not pass.
+    Error(String),
+}
+
+/// Record of a scripted run.
+#[derive(Debug)]
+pub struct Transcript<T> {
+    /// Value that `.try_read()` returned.
+    ///
+    /// Running out of script lines is treated as reaching the end of input.
+    pub result: Result<T, ReadInputError>,
+    /// Every prompt, input line and error in the order they happened.
+    pub events: Vec<Event>,
+}
+
+impl<T> Transcript<T> {
+    /// Number of times input was rejected and asked for again.
+    pub fn retries(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|event| matches!(event, Event::Error(_)))
+            .count()
+    }
+    /// Renders the events the way they would have appeared on a terminal.
+    pub fn output(&self) -> String {
+        let mut output = String::new();
+        for event in &self.events {
+            match event {
+                Event::Prompt(msg) => output.push_str(msg),
+                Event::Input(line) | Event::Error(line) => {
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            }
+        }
+        output
+    }
+}
+
+/// Fetches input with the settings of an `InputBuilder`, reading lines from `script`.
+pub fn run<T, E>(builder: &InputBuilder<T, E>, script: &[&str]) -> Transcript<T> {
+    let mut console = Scripted::new(script);
+    let result = builder.read_on(None, &mut console);
+    console.finish(result)
+}
+
+/// Fetches input with the settings of an `InputBuilderOnce`, reading lines from `script`.
+pub fn run_once<T, E>(builder: InputBuilderOnce<T, E>, script: &[&str]) -> Transcript<T> {
+    let mut console = Scripted::new(script);
+    let result = builder
+        .into_parts()
+        .and_then(|(builder, default)| builder.read_on(default, &mut console));
+    console.finish(result)
+}
+
+struct Scripted<'a> {
+    script: std::slice::Iter<'a, &'a str>,
+    events: Vec<Event>,
+}
+
+impl<'a> Scripted<'a> {
+    fn new(script: &'a [&'a str]) -> Self {
+        Self {
+            script: script.iter(),
+            events: Vec::new(),
+        }
+    }
+    fn finish<T>(self, result: Result<T, ReadInputError>) -> Transcript<T> {
+        Transcript {
+            result,
+            events: self.events,
+        }
+    }
+}
+
+impl Console for Scripted<'_> {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        Ok(self.script.next().map(|line| {
+            self.events.push(Event::Input((*line).to_string()));
+            format!("{line}\n")
+        }))
+    }
+    fn prompt(&mut self, msg: &str) {
+        if !msg.is_empty() {
+            self.events.push(Event::Prompt(msg.to_string()));
+        }
+    }
+    fn error(&mut self, msg: &str) {
+        self.events.push(Event::Error(msg.to_string()));
+    }
+}
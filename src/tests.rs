@@ -1,8 +1,25 @@
-use crate::{core::parse_input, shortcut::input, InputBuild, InputBuilder};
-use std::str::FromStr;
+use crate::{
+    core::{parse_input, Console},
+    echo::read_masked,
+    shortcut::{
+        confirm, input, input_list, input_tuple, input_with, multi_select, multi_select_index,
+        select, select_index, tuple,
+    },
+    testing::{run, run_once, Event},
+    InputBuild, InputBuilder, InputConstraints, ListConstraints, ReadInputError, ScanError,
+    Scanner,
+};
+use std::{
+    io::{self, Cursor},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-fn parse_with_builder<T: FromStr>(builder: InputBuilder<T>, input: String) -> Result<T, String> {
-    parse_input(input, &builder.err, &builder.tests, &*builder.err_match)
+fn parse_with_builder<T, E>(builder: InputBuilder<T, E>, input: String) -> Result<T, String> {
+    parse_input(input, &builder)
 }
 
 #[test]
@@ -132,3 +149,715 @@ fn test_array() {
         Err("1".to_string())
     );
 }
+
+#[test]
+fn test_reading_from() {
+    let builder = input::<u32>()
+        .prompting_on(io::sink())
+        .reading_from(Cursor::new("a\n7\n8\n9\n"));
+    assert_eq!(builder.try_get().unwrap(), 7);
+    assert_eq!(builder.try_get().unwrap(), 8);
+    // Clones keep reading from the same source rather than stdin.
+    assert_eq!(builder.clone().try_get().unwrap(), 9);
+}
+
+#[test]
+fn test_scripted_msg() {
+    let transcript = run(&input::<u8>().msg("> ").err("1"), &["300", "", "3"]);
+    assert_eq!(transcript.retries(), 2);
+    assert_eq!(transcript.output(), "> 300\n1\n\n1\n3\n");
+    assert_eq!(transcript.result.unwrap(), 3);
+}
+
+#[test]
+fn test_scripted_default() {
+    let transcript = run_once(
+        input().repeat_msg("> ").inside(1..4).err("1").default(8),
+        &["9", " "],
+    );
+    assert_eq!(transcript.result.unwrap(), 8);
+    assert_eq!(
+        transcript.events,
+        vec![
+            Event::Prompt("> ".to_string()),
+            Event::Input("9".to_string()),
+            Event::Error("1".to_string()),
+            Event::Prompt("> ".to_string()),
+            Event::Input(" ".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_scripted_runs_out() {
+    let transcript = run(&input::<u8>(), &["a"]);
+    assert!(matches!(transcript.result, Err(ReadInputError::Eof)));
+}
+
+#[test]
+fn test_eof() {
+    let builder = input::<u8>()
+        .prompting_on(io::sink())
+        .reading_from(Cursor::new("a\n"));
+    assert!(matches!(builder.try_read(), Err(ReadInputError::Eof)));
+    assert_eq!(
+        builder.try_get().unwrap_err().kind(),
+        io::ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn test_eof_default() {
+    assert_eq!(run_once(input().default(1), &["a"]).result.unwrap(), 1);
+    assert!(matches!(
+        run_once(input().default(1).eof_default(false), &["a"]).result,
+        Err(ReadInputError::Eof)
+    ));
+}
+
+#[test]
+fn test_error_kinds() {
+    assert!(matches!(
+        ReadInputError::from(io::Error::from(io::ErrorKind::BrokenPipe)),
+        ReadInputError::Closed(_)
+    ));
+    assert!(matches!(
+        ReadInputError::from(io::Error::from(io::ErrorKind::Interrupted)),
+        ReadInputError::Interrupted
+    ));
+    assert_eq!(
+        io::Error::from(ReadInputError::Interrupted).kind(),
+        io::ErrorKind::Interrupted
+    );
+}
+
+#[test]
+fn test_attempts() {
+    let transcript = run(&input::<u8>().attempts(2).err("1"), &["a", " b ", "3"]);
+    assert_eq!(transcript.retries(), 2);
+    match transcript.result {
+        Err(ReadInputError::TooManyAttempts { input, message }) => {
+            assert_eq!(input, "b");
+            assert_eq!(message, "1");
+        }
+        _ => panic!("expected TooManyAttempts"),
+    }
+    assert_eq!(
+        run(&input::<u8>().attempts(2), &["a", "3"]).result.unwrap(),
+        3
+    );
+
+    // Hidden input is left out of the error, including a password that was not confirmed.
+    let builder = input::<String>()
+        .hidden()
+        .add_test(|x| x.len() > 3)
+        .attempts(1);
+    assert!(matches!(
+        run(&builder, &["abc"]).result,
+        Err(ReadInputError::TooManyAttempts { input, .. }) if input.is_empty()
+    ));
+    let builder = input::<String>()
+        .masked('*')
+        .confirm_password("")
+        .attempts(1);
+    assert!(matches!(
+        run(&builder, &["hunter2", "hunter3"]).result,
+        Err(ReadInputError::TooManyAttempts { input, .. }) if input.is_empty()
+    ));
+
+    // The confirmation that did not match is the rejected input.
+    let builder = input::<String>().confirm_password("Again: ").attempts(1);
+    assert!(matches!(
+        run(&builder, &["a", " b "]).result,
+        Err(ReadInputError::TooManyAttempts { input, .. }) if input == "b"
+    ));
+}
+
+#[test]
+fn test_check() {
+    let builder = input::<u16>()
+        .add_check(|x| {
+            if *x == 22 {
+                Err(format!("port {} is reserved, {} is free", x, x + 8000))
+            } else {
+                Ok(())
+            }
+        })
+        .err("1");
+    assert_eq!(
+        parse_with_builder(builder.clone(), "22".to_string()),
+        Err("port 22 is reserved, 8022 is free".to_string())
+    );
+    assert_eq!(
+        parse_with_builder(builder.clone(), "a".to_string()),
+        Err("1".to_string())
+    );
+    assert_eq!(parse_with_builder(builder, "80".to_string()), Ok(80));
+}
+
+#[test]
+fn test_map() {
+    let builder = input::<u8>().inside(1..=9).err("1").map(|x| x * 10);
+    assert_eq!(parse_with_builder(builder.clone(), "3".to_string()), Ok(30));
+    assert_eq!(
+        parse_with_builder(builder, "10".to_string()),
+        Err("1".to_string())
+    );
+}
+
+#[test]
+fn test_and_then() {
+    let builder = input::<String>()
+        .and_then(|s| s.parse::<u8>().map_err(|_| format!("{s} is not a byte")))
+        .add_test(|x| *x != 0)
+        .err("1");
+    assert_eq!(parse_with_builder(builder.clone(), "7".to_string()), Ok(7));
+    assert_eq!(
+        parse_with_builder(builder.clone(), "300".to_string()),
+        Err("300 is not a byte".to_string())
+    );
+    assert_eq!(
+        parse_with_builder(builder, "0".to_string()),
+        Err("1".to_string())
+    );
+}
+
+#[test]
+fn test_input_with() {
+    let builder = input_with(|s| match s.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => s.parse(),
+    })
+    .err_match(|_| Some("1".to_string()))
+    .min(10);
+    assert_eq!(
+        parse_with_builder(builder.clone(), "0x1f".to_string()),
+        Ok(31)
+    );
+    assert_eq!(
+        parse_with_builder(builder.clone(), "12".to_string()),
+        Ok(12)
+    );
+    assert_eq!(
+        parse_with_builder(builder.clone(), "0xz".to_string()),
+        Err("1".to_string())
+    );
+    assert_eq!(
+        parse_with_builder(builder, "3".to_string()),
+        Err(crate::DEFAULT_ERR.to_string())
+    );
+}
+
+#[test]
+fn test_raw_steps() {
+    let builder = input::<u32>()
+        .add_raw_check(|s| {
+            if s.len() > 5 {
+                Err("1".to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .normalize(|s| s.replace(',', ""));
+    assert_eq!(
+        parse_with_builder(builder.clone(), " 1,000 ".to_string()),
+        Ok(1000)
+    );
+    assert_eq!(
+        parse_with_builder(builder, "1,000,000".to_string()),
+        Err("1".to_string())
+    );
+}
+
+#[test]
+fn test_confirm_password() {
+    let builder = input::<String>()
+        .msg("Password: ")
+        .confirm_password("Again: ")
+        .hidden();
+    let transcript = run(&builder, &["hunter2", "hunter3", "hunter2", "hunter2"]);
+    assert_eq!(transcript.result.as_deref().unwrap(), "hunter2");
+    assert_eq!(
+        transcript.events,
+        vec![
+            Event::Prompt("Password: ".to_string()),
+            Event::Input("hunter2".to_string()),
+            Event::Prompt("Again: ".to_string()),
+            Event::Input("hunter3".to_string()),
+            Event::Error("Inputs do not match. Please try again".to_string()),
+            Event::Input("hunter2".to_string()),
+            Event::Prompt("Again: ".to_string()),
+            Event::Input("hunter2".to_string()),
+        ]
+    );
+    assert!(matches!(
+        run(&builder, &["hunter2"]).result,
+        Err(ReadInputError::Eof)
+    ));
+}
+
+#[test]
+fn test_read_masked() {
+    let mut prompt_output = Vec::new();
+    let line = read_masked(&mut Cursor::new("pä\x7fss\nrest"), &mut prompt_output, '*').unwrap();
+    assert_eq!(line.as_deref(), Some("pss"));
+    assert_eq!(prompt_output, "**\u{8} \u{8}**".as_bytes());
+    assert_eq!(
+        read_masked(&mut Cursor::new("\x04"), &mut io::sink(), '*').unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_confirm() {
+    let transcript = run_once(confirm("Delete?").default(true), &["maybe", ""]);
+    assert!(*transcript.result.as_ref().unwrap());
+    assert_eq!(
+        transcript.output(),
+        "Delete? [Y/n] maybe\nPlease type yes or no.\n\n"
+    );
+    let transcript = run(&confirm("Delete?").repeat_msg("Delete?"), &["YES"]);
+    assert!(*transcript.result.as_ref().unwrap());
+    assert_eq!(transcript.output(), "Delete? [y/n] YES\n");
+    assert!(!run_once(confirm("Delete?").default(false), &["No"])
+        .result
+        .unwrap());
+}
+
+#[test]
+fn test_select() {
+    let builder = select(vec!["red", "green", "blue"]).msg("Colour: ");
+    let transcript = run(&builder, &["4", "purple", "2"]);
+    assert_eq!(transcript.retries(), 2);
+    assert_eq!(
+        transcript.output(),
+        "1) red\n2) green\n3) blue\nColour: 4\nPlease type the number or name of an option.\n\
+         purple\nPlease type the number or name of an option.\n2\n"
+    );
+    assert_eq!(transcript.result.unwrap(), "green");
+    assert_eq!(run(&builder, &["blue"]).result.unwrap(), "blue");
+    assert_eq!(
+        run(&select_index(&[10, 20, 30]), &["30"]).result.unwrap(),
+        2
+    );
+
+    // The menu is still printed after the output type is changed.
+    let builder = select(vec!["red", "blue"]).msg("Colour: ").map(str::len);
+    let transcript = run(&builder, &["1"]);
+    assert_eq!(transcript.output(), "1) red\n2) blue\nColour: 1\n");
+    assert_eq!(transcript.result.unwrap(), 3);
+    let builder = confirm("Delete?").map(|x| !x);
+    assert_eq!(run(&builder, &["y"]).output(), "Delete? [y/n] y\n");
+}
+
+#[test]
+fn test_multi_select() {
+    let builder = multi_select(vec!["a", "b", "c", "d", "e"])
+        .max_len_err(3, "2")
+        .err("1");
+    let transcript = run(&builder, &["1-6", "3-1", "x", "1-4", "5, 1-2 2"]);
+    assert_eq!(transcript.retries(), 4);
+    assert_eq!(
+        transcript
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Error(err) => Some(err.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        vec!["1", "1", "1", "2"]
+    );
+    assert_eq!(transcript.result.unwrap(), vec!["a", "b", "e"]);
+    assert_eq!(
+        run(&multi_select_index(&["a", "b"]).min_len(1), &["", "2"])
+            .result
+            .unwrap(),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_list() {
+    let builder = input::<u8>()
+        .inside_err(1..=6, "not a dice roll")
+        .err_match(|_| Some("not a number".to_string()))
+        .list(",")
+        .min_len(2)
+        .unique_err("duplicate")
+        .err("1");
+    assert_eq!(
+        parse_input("1, 2,x".to_string(), &builder),
+        Err("Value 3 (x): not a number".to_string())
+    );
+    assert_eq!(
+        parse_input("1,7".to_string(), &builder),
+        Err("Value 2 (7): not a dice roll".to_string())
+    );
+    assert_eq!(parse_input("3".to_string(), &builder), Err("1".to_string()));
+    assert_eq!(
+        parse_input("3, 5, 3".to_string(), &builder),
+        Err("duplicate".to_string())
+    );
+    assert_eq!(parse_input("6, 1".to_string(), &builder), Ok(vec![6, 1]));
+
+    // Whitespace around the separator is optional.
+    let builder = input::<u8>().list(", ");
+    assert_eq!(
+        parse_input("1,2 , 3".to_string(), &builder),
+        Ok(vec![1, 2, 3])
+    );
+
+    let builder = input_list::<i32>().sorted();
+    assert_eq!(
+        parse_input(" 1  2\t3 ".to_string(), &builder),
+        Ok(vec![1, 2, 3])
+    );
+    assert!(parse_input("2 1".to_string(), &builder).is_err());
+    assert_eq!(parse_input(String::new(), &builder), Ok(vec![]));
+}
+
+#[test]
+fn test_tuple() {
+    let builder = tuple(
+        (
+            input::<i32>().err_match(|_| Some("bad x".to_string())),
+            input::<i32>().inside_err(0.., "negative y"),
+        ),
+        ",",
+    );
+    assert_eq!(parse_input("1, 2".to_string(), &builder), Ok((1, 2)));
+    assert_eq!(
+        parse_input("a,2".to_string(), &builder),
+        Err("Value 1 (a): bad x".to_string())
+    );
+    assert_eq!(
+        parse_input("1, -2".to_string(), &builder),
+        Err("Value 2 (-2): negative y".to_string())
+    );
+    assert_eq!(
+        parse_input("1,2,3".to_string(), &builder),
+        Err("Please input 2 values, not 3.".to_string())
+    );
+    assert_eq!(
+        parse_input(
+            "x 1.5 true".to_string(),
+            &input_tuple::<(char, f32, bool)>(" ")
+        ),
+        Ok(('x', 1.5, true))
+    );
+    let builder = input::<u8>().array::<3>(".");
+    assert_eq!(parse_input("1.2.3".to_string(), &builder), Ok([1, 2, 3]));
+    assert_eq!(
+        parse_input("1.2".to_string(), &builder),
+        Err("Please input 3 values, not 2.".to_string())
+    );
+}
+
+#[test]
+fn test_input_macro() {
+    let builder = crate::input!("{} x {}", u32, u32).err("1");
+    assert_eq!(parse_input("3 x 4".to_string(), &builder), Ok((3, 4)));
+    assert_eq!(parse_input("3x4".to_string(), &builder), Ok((3, 4)));
+    assert_eq!(
+        parse_input("3 by 4".to_string(), &builder),
+        Err("1".to_string())
+    );
+    assert_eq!(
+        parse_input("3 x a".to_string(), &builder),
+        Err("1".to_string())
+    );
+    assert_eq!(
+        parse_input("(1, 2.5)".to_string(), &crate::input!("({}, {})", i8, f32)),
+        Ok((1, 2.5))
+    );
+    assert_eq!(
+        parse_input("a   b".to_string(), &crate::input!("{} {}", char, char)),
+        Ok(('a', 'b'))
+    );
+    let transcript = run(&crate::input!("{}%", u8).msg("Volume: "), &["50", "50%"]);
+    assert_eq!(
+        transcript.output(),
+        "Volume: 50\nPlease input a line in the format \"{}%\".\n50%\n"
+    );
+    assert_eq!(transcript.result.unwrap(), 50);
+
+    // Literals that also appear inside values.
+    let builder = crate::input!("{}-{}", i32, i32);
+    assert_eq!(parse_input("-1--2".to_string(), &builder), Ok((-1, -2)));
+    assert_eq!(parse_input("3-4".to_string(), &builder), Ok((3, 4)));
+    assert_eq!(
+        parse_input("a-4".to_string(), &builder),
+        Err("Please input a line in the format \"{}-{}\".".to_string())
+    );
+    assert_eq!(
+        parse_input(
+            "New York 5".to_string(),
+            &crate::input!("{} {}", String, u8)
+        ),
+        Ok(("New York".to_string(), 5))
+    );
+}
+
+#[test]
+fn test_scanner() {
+    let mut scanner = Scanner::new(Cursor::new("3\n  1 2\t-3\n\nx 4.5"));
+    let n: usize = scanner.get();
+    assert_eq!(scanner.get_vec::<i32>(n), vec![1, 2, -3]);
+    match scanner.try_get::<u8>() {
+        Err(ScanError::Parse { token, .. }) => assert_eq!(token, "x"),
+        _ => panic!("expected Parse"),
+    }
+    assert_eq!(scanner.get::<String>(), "4.5");
+    assert!(matches!(
+        scanner.try_get::<u8>(),
+        Err(ScanError::Read(ReadInputError::Eof))
+    ));
+}
+
+// Console that never receives input before the time limit.
+struct Idle;
+
+impl Console for Idle {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        Err(io::ErrorKind::TimedOut.into())
+    }
+    fn prompt(&mut self, _msg: &str) {}
+    fn error(&mut self, _msg: &str) {}
+}
+
+#[test]
+fn test_timeout() {
+    let builder = input::<u8>().timeout(Duration::from_secs(1));
+    assert!(matches!(
+        builder.read_on(None, &mut Idle),
+        Err(ReadInputError::TimedOut)
+    ));
+    assert_eq!(builder.read_on(Some(5), &mut Idle).unwrap(), 5);
+    // A source that times out by itself is an error even when there is a default.
+    assert!(matches!(
+        input::<u8>().read_on(Some(5), &mut Idle),
+        Err(ReadInputError::TimedOut)
+    ));
+    assert_eq!(
+        io::Error::from(ReadInputError::TimedOut).kind(),
+        io::ErrorKind::TimedOut
+    );
+}
+
+#[test]
+fn test_cancel() {
+    let builder = input::<u8>().cancel_word(":q").default(3);
+    assert!(matches!(
+        run_once(builder, &["a", " :q "]).result,
+        Err(ReadInputError::Cancelled)
+    ));
+
+    // Catching Ctrl-C turns an interrupted read into a cancellation.
+    let builder = input::<u8>().cancel_on_interrupt();
+    assert!(matches!(
+        builder.read_on(None, &mut Ctrl),
+        Err(ReadInputError::Cancelled)
+    ));
+    assert!(matches!(
+        input::<u8>().read_on(None, &mut Ctrl),
+        Err(ReadInputError::Interrupted)
+    ));
+
+    let error = io::Error::from(ReadInputError::Cancelled);
+    assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+    assert!(matches!(
+        ReadInputError::from(error),
+        ReadInputError::Cancelled
+    ));
+}
+
+// Console where Ctrl-C is pressed before any input is entered.
+struct Ctrl;
+
+impl Console for Ctrl {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        Err(io::ErrorKind::Interrupted.into())
+    }
+    fn prompt(&mut self, _msg: &str) {}
+    fn error(&mut self, _msg: &str) {}
+}
+
+#[test]
+fn test_show_default() {
+    let builder = input::<u16>()
+        .repeat_msg("Port: ")
+        .err("Bad port")
+        .default(8080)
+        .show_default();
+    let transcript = run_once(builder, &["a", ""]);
+    assert_eq!(transcript.result.unwrap(), 8080);
+    assert_eq!(
+        transcript.events,
+        vec![
+            Event::Prompt("Port [8080]: ".to_string()),
+            Event::Input("a".to_string()),
+            Event::Error("Bad port".to_string()),
+            Event::Prompt("Port [8080]: ".to_string()),
+            Event::Input(String::new()),
+        ]
+    );
+
+    let builder = select(vec!["red", "blue"])
+        .msg("Colour? ")
+        .default("blue")
+        .show_default_as(" (default {})");
+    let transcript = run_once(builder, &[""]);
+    assert_eq!(
+        transcript.events[0],
+        Event::Prompt("1) red\n2) blue\nColour (default blue)? ".to_string())
+    );
+
+    // Setting the template again replaces it, and an empty message is left empty.
+    let builder = input::<u8>()
+        .msg("> ")
+        .default(3)
+        .show_default()
+        .show_default();
+    assert_eq!(run_once(builder, &[""]).output(), "[3]> \n");
+    let builder = input::<u8>().default(4).show_default();
+    assert_eq!(run_once(builder, &[""]).output(), "\n");
+}
+
+#[test]
+fn test_default_with() {
+    let calls = Arc::new(AtomicU32::new(0));
+    let counter = calls.clone();
+    let builder = input::<u32>()
+        .msg("Count: ")
+        .default_with(move || counter.fetch_add(1, Ordering::SeqCst) + 10)
+        .show_default();
+    let transcript = run(&builder, &[""]);
+    assert_eq!(transcript.result.unwrap(), 10);
+    assert_eq!(
+        transcript.events[0],
+        Event::Prompt("Count [10]: ".to_string())
+    );
+    assert_eq!(run(&builder, &[""]).result.unwrap(), 11);
+    assert_eq!(run(&builder, &["3"]).result.unwrap(), 3);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    // A default set with `.default()` is used instead.
+    assert_eq!(run_once(builder.default(1), &[""]).result.unwrap(), 1);
+}
+
+#[test]
+fn test_checked_default() {
+    assert_eq!(
+        input::<u32>().min_err(10, "Too small").try_default(3).err(),
+        Some("Too small".to_string())
+    );
+    assert_eq!(
+        input::<u32>().min(10).err("1").try_default(3).err(),
+        Some("1".to_string())
+    );
+    let builder = input::<u32>().min(10).try_default(12).unwrap();
+    assert_eq!(run_once(builder, &[""]).result.unwrap(), 12);
+    // Tests added after `.try_default()` are checked when input is requested.
+    let builder = input::<u32>().err("1").try_default(3).unwrap().min(10);
+    let transcript = run_once(builder, &["12"]);
+    assert!(transcript.events.is_empty());
+    assert!(matches!(
+        transcript.result,
+        Err(ReadInputError::InvalidDefault(message)) if message == "1"
+    ));
+
+    // So are computed defaults, before the prompt is shown.
+    let builder = input::<u32>()
+        .msg("> ")
+        .min_err(10, "Too small")
+        .default_with(|| 3)
+        .show_default();
+    let transcript = run(&builder, &["11"]);
+    assert!(transcript.events.is_empty());
+    assert!(matches!(
+        transcript.result,
+        Err(ReadInputError::InvalidDefault(message)) if message == "Too small"
+    ));
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_send_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    let builder = input::<u32>().min(3).add_test(|x| *x != 5).map(|x| x * 2);
+    assert_send_sync(&builder);
+    assert_send_sync(&builder.default(4));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_read_async() {
+    let mut prompt_output = Vec::new();
+    let value = input::<u8>()
+        .repeat_msg("> ")
+        .err("1")
+        .try_read_async(&mut "a\n4\n".as_bytes(), &mut prompt_output)
+        .await
+        .unwrap();
+    assert_eq!(value, 4);
+    assert_eq!(prompt_output, b"> 1\n> ");
+
+    let value = input::<u8>()
+        .default(3)
+        .try_read_async(&mut "".as_bytes(), &mut tokio::io::sink())
+        .await;
+    assert_eq!(value.unwrap(), 3);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test(start_paused = true)]
+async fn test_read_async_timeout() {
+    let (_writer, reader) = tokio::io::duplex(64);
+    let mut input_source = tokio::io::BufReader::new(reader);
+    let value = input::<u8>()
+        .timeout(Duration::from_secs(5))
+        .default(7)
+        .try_read_async(&mut input_source, &mut tokio::io::sink())
+        .await;
+    assert_eq!(value.unwrap(), 7);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_async_tests() {
+    let mut prompt_output = Vec::new();
+    let value = input::<String>()
+        .err("1")
+        .add_async_test(|name| async move { name != "root" })
+        .add_async_check(|name| async move {
+            if name.len() < 3 {
+                Err(format!("{name} is too short"))
+            } else {
+                Ok(())
+            }
+        })
+        .try_read_async(&mut "root\nab\nabc\n".as_bytes(), &mut prompt_output)
+        .await
+        .unwrap();
+    assert_eq!(value, "abc");
+    assert_eq!(prompt_output, b"1\nab is too short\n");
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn test_async_tests_blocking() {
+    let builder = input::<u8>().add_async_test(|_| async { true });
+    assert!(matches!(
+        run(&builder, &["1"]).result,
+        Err(ReadInputError::NeedsAsync)
+    ));
+    // The error survives the round trip through `io::Error` used by `.try_get()`.
+    let error = builder
+        .reading_from(Cursor::new("1\n"))
+        .try_get()
+        .unwrap_err();
+    assert!(matches!(
+        ReadInputError::from(error),
+        ReadInputError::NeedsAsync
+    ));
+}
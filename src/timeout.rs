@@ -0,0 +1,110 @@
+// Waiting for input with a time limit. Used by `.timeout()` and `.cancel_on_interrupt()`.
+//
+// Stdin can only be waited on when it is a terminal on Unix. Everywhere else input is
+// read without a time limit.
+
+#[cfg(unix)]
+use crate::interrupt;
+#[cfg(unix)]
+use std::{convert::TryFrom, io::Write, time::Instant};
+use std::{io, time::Duration};
+
+// Blocks until a line can be read from stdin. Fails with `io::ErrorKind::TimedOut` once
+// `timeout` has passed, or waits forever if it is `None`. With `countdown` set the
+// seconds left are shown after the prompt, which ends at `column` on the current line.
+// Fails with `io::ErrorKind::Interrupted` if Ctrl-C is caught while waiting.
+#[cfg(unix)]
+pub(crate) fn wait_for_stdin(
+    timeout: Option<Duration>,
+    prompt_output: &mut dyn Write,
+    countdown: bool,
+    column: usize,
+) -> io::Result<()> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    // Width of the seconds shown, set once the countdown is first drawn.
+    let mut width = None;
+    loop {
+        let Some(deadline) = deadline else {
+            poll_stdin(None)?;
+            return Ok(());
+        };
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            // Text typed without pressing enter is still in the terminal's input queue.
+            // It is thrown away so it is not read as the answer to the next prompt.
+            // SAFETY: `tcflush` only takes a file descriptor and a flag.
+            unsafe {
+                libc::tcflush(libc::STDIN_FILENO, libc::TCIFLUSH);
+            }
+            let _ = writeln!(prompt_output);
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        let wait = if countdown {
+            let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+            if let Some(width) = width {
+                // Text typed so far is only in the terminal's line buffer, so it must not
+                // be overwritten. The countdown is redrawn in front of it and the cursor
+                // is put back.
+                let _ = write!(prompt_output, "\x1b7\r");
+                if column > 0 {
+                    let _ = write!(prompt_output, "\x1b[{column}C");
+                }
+                let _ = write!(prompt_output, "({secs:>width$}s)\x1b8");
+            } else {
+                let _ = write!(prompt_output, "({secs}s) ");
+                width = Some(secs.to_string().len());
+            }
+            let _ = prompt_output.flush();
+            // Wake up when the number of seconds shown changes.
+            left.saturating_sub(Duration::from_secs(secs - 1))
+        } else {
+            left
+        };
+        if poll_stdin(Some(wait))? {
+            return Ok(());
+        }
+    }
+}
+
+// Waits up to `wait` for stdin to be readable. Returns `false` if it is not readable yet.
+#[cfg(unix)]
+fn poll_stdin(wait: Option<Duration>) -> io::Result<bool> {
+    let millis = wait.map_or(-1, |wait| {
+        i32::try_from(wait.as_millis()).unwrap_or(i32::MAX).max(1)
+    });
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    loop {
+        // SAFETY: `fd` is a valid pointer to one `pollfd` for the duration of the call.
+        match unsafe { libc::poll(&raw mut fd, 1, millis) } {
+            0 => return Ok(false),
+            -1 => {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted || interrupt::take() {
+                    return Err(error);
+                }
+                // Another signal arrived. The deadline is checked by the caller.
+                if wait.is_some() {
+                    return Ok(false);
+                }
+            }
+            _ => return Ok(true),
+        }
+    }
+}
+
+// Without a way to wait on stdin input is read without a time limit. Returns a `Result`
+// like the Unix version.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+pub(crate) fn wait_for_stdin(
+    _timeout: Option<Duration>,
+    _prompt_output: &mut dyn io::Write,
+    _countdown: bool,
+    _column: usize,
+) -> io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use crate::{
+    core::parse_input, count_err, shortcut::input, split_values, value_err, InputBuild,
+    InputBuilder, Rejection, Shared,
+};
+use std::{error::Error, fmt, str::FromStr};
+
+/// Error produced by `tuple()` builders when input has the wrong number of values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountError {
+    /// Number of values needed.
+    pub expected: usize,
+    /// Number of values found.
+    pub found: usize,
+}
+
+impl fmt::Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} values but found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for CountError {}
+
+// Stops `TupleBuild` being implemented outside this crate, so its methods can change.
+mod private {
+    pub trait Sealed {}
+}
+
+/// Trait for tuples of builders that are combined by `shortcut::tuple()`.
+///
+/// This trait is sealed. It is only implemented for tuples of up to 6 `InputBuilder`s.
+pub trait TupleBuild: private::Sealed {
+    /// Tuple of the types returned by each builder.
+    type Output;
+    /// Number of builders in the tuple.
+    const LEN: usize;
+
+    // Parses each value with its builder. Returns the error message for the first value
+    // that fails, or if `values` does not have `LEN` values.
+    #[doc(hidden)]
+    fn parse_values(&self, values: &[&str]) -> Result<Self::Output, String>;
+    // Checks the value at index `i` with its builder without keeping the result.
+    #[doc(hidden)]
+    fn check_value(&self, i: usize, value: &str) -> Result<(), String>;
+}
+
+/// Trait for tuples of `FromStr` types read by `shortcut::input_tuple()`.
+pub trait TupleInput: Sized {
+    /// Tuple of builders that read each type.
+    type Builders: TupleBuild<Output = Self>;
+
+    /// Returns a builder with generic, minimal settings for each type.
+    fn builders() -> Self::Builders;
+}
+
+macro_rules! impl_tuple {
+    ($len:expr; $($t:ident $e:ident $i:tt),*) => {
+        impl<$($t, $e),*> private::Sealed for ($(InputBuilder<$t, $e>,)*) {}
+
+        impl<$($t, $e),*> TupleBuild for ($(InputBuilder<$t, $e>,)*) {
+            type Output = ($($t,)*);
+            const LEN: usize = $len;
+
+            fn parse_values(&self, values: &[&str]) -> Result<Self::Output, String> {
+                if values.len() != Self::LEN {
+                    return Err(count_err(Self::LEN, values.len()));
+                }
+                Ok(($(
+                    parse_input(values[$i].to_string(), &self.$i)
+                        .map_err(|message| value_err($i, values[$i], &message))?,
+                )*))
+            }
+
+            fn check_value(&self, i: usize, value: &str) -> Result<(), String> {
+                match i {
+                    $($i => parse_input(value.to_string(), &self.$i)
+                        .map(drop)
+                        .map_err(|message| value_err($i, value, &message)),)*
+                    _ => Err(count_err(Self::LEN, i + 1)),
+                }
+            }
+        }
+
+        impl<$($t: FromStr),*> TupleInput for ($($t,)*) {
+            type Builders = ($(InputBuilder<$t, $t::Err>,)*);
+
+            fn builders() -> Self::Builders {
+                ($(impl_tuple!(@input $i),)*)
+            }
+        }
+    };
+    (@input $i:tt) => {
+        input()
+    };
+}
+
+impl_tuple! { 1; A EA 0 }
+impl_tuple! { 2; A EA 0, B EB 1 }
+impl_tuple! { 3; A EA 0, B EB 1, C EC 2 }
+impl_tuple! { 4; A EA 0, B EB 1, C EC 2, D ED 3 }
+impl_tuple! { 5; A EA 0, B EB 1, C EC 2, D ED 3, F EF 4 }
+impl_tuple! { 6; A EA 0, B EB 1, C EC 2, D ED 3, F EF 4, G EG 5 }
+
+// Creates the builder returned by `shortcut::scan()`. `format` has a `{}` for each value.
+pub(crate) fn scan<T>(format: &str) -> InputBuilder<T, ()>
+where
+    T: TupleInput,
+    T::Builders: crate::Shareable + 'static,
+{
+    let literals: Vec<String> = format.split("{}").map(ToString::to_string).collect();
+    assert_eq!(
+        literals.len() - 1,
+        T::Builders::LEN,
+        "`{format}` needs a `{{}}` for each type"
+    );
+    assert!(
+        literals[1..literals.len() - 1]
+            .iter()
+            .all(|literal| !literal.is_empty()),
+        "`{}` must have text between each `{{}}`",
+        format
+    );
+    let builders = T::builders();
+    // A value that fails is reported with the `.err()` message, since the builders used
+    // for each value can't be changed.
+    InputBuilder::from_parser(Shared::new(move |input: &str| {
+        let values = scan_values(input, &literals, &builders).ok_or(Rejection::Msg(None))?;
+        builders
+            .parse_values(&values)
+            .map_err(|_| Rejection::Msg(None))
+    }))
+    .err(format!("Please input a line in the format \"{format}\"."))
+}
+
+// Finds the values between `literals` in `input`. A literal can also appear inside a
+// value, such as the `-` in `-1--2` for `{}-{}`, so each place it is found is tried until
+// the value before it passes. Whitespace around literals is ignored and a literal made of
+// whitespace matches any amount of whitespace.
+//
+// Returns `None` if the line does not match the format or no way of splitting it passes.
+fn scan_values<'a, B: TupleBuild>(
+    input: &'a str,
+    literals: &[String],
+    builders: &B,
+) -> Option<Vec<&'a str>> {
+    let rest = input.strip_prefix(literals[0].trim())?;
+    let mut values = Vec::new();
+    scan_rest(rest, &literals[1..], builders, &mut values).then_some(values)
+}
+
+// Matches the values after those already in `values`. Returns `false` if no way of
+// splitting `rest` passes.
+fn scan_rest<'a, B: TupleBuild>(
+    rest: &'a str,
+    literals: &[String],
+    builders: &B,
+    values: &mut Vec<&'a str>,
+) -> bool {
+    let Some((literal, literals)) = literals.split_first() else {
+        return rest.trim().is_empty();
+    };
+    for (value, next) in splits(rest, literal) {
+        let value = value.trim();
+        if builders.check_value(values.len(), value).is_ok() {
+            values.push(value);
+            if scan_rest(next, literals, builders, values) {
+                return true;
+            }
+            values.pop();
+        }
+    }
+    false
+}
+
+// Each way of splitting `rest` into a value and the text after `literal`.
+fn splits<'a>(rest: &'a str, literal: &str) -> Vec<(&'a str, &'a str)> {
+    if literal.is_empty() {
+        vec![(rest, "")]
+    } else if literal.trim().is_empty() {
+        rest.char_indices()
+            .filter(|&(i, c)| c.is_whitespace() && !rest[..i].ends_with(char::is_whitespace))
+            .map(|(i, _)| (&rest[..i], rest[i..].trim_start()))
+            .collect()
+    } else {
+        let literal = literal.trim();
+        rest.match_indices(literal)
+            .map(|(i, _)| (&rest[..i], &rest[i + literal.len()..]))
+            .collect()
+    }
+}
+
+// Creates the builder returned by `shortcut::tuple()`.
+pub(crate) fn tuple<B>(builders: B, separator: String) -> InputBuilder<B::Output, CountError>
+where
+    B: TupleBuild + crate::Shareable + 'static,
+{
+    InputBuilder::from_parser(Shared::new(move |input: &str| {
+        let values = split_values(input, &separator);
+        if values.len() != B::LEN {
+            return Err(Rejection::Parse(CountError {
+                expected: B::LEN,
+                found: values.len(),
+            }));
+        }
+        builders
+            .parse_values(&values)
+            .map_err(|message| Rejection::Msg(Some(message)))
+    }))
+    .err_match(|e: &CountError| Some(count_err(e.expected, e.found)))
+}